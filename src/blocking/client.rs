@@ -0,0 +1,406 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+use super::{RequestBuilder, Response};
+use crate::{
+    Method, Request, Result,
+    error::BuilderError,
+    header::{HeaderMap, HeaderValue},
+};
+
+/// A blocking `Client` to make Requests over Unix socket with.
+///
+/// The `Client` drives an internal Tokio runtime, which is shared by all of
+/// its clones and by the [`Response`]s it returns.
+///
+/// Like the asynchronous [`Client`](crate::Client), it holds a connection
+/// pool internally, so it is advised that you create one and **reuse** it.
+///
+/// # Panics
+///
+/// The `Client` must not be created, used or dropped within an async runtime.
+#[derive(Debug, Clone)]
+pub struct Client {
+    pub(super) inner: crate::Client,
+    runtime: Arc<Runtime>,
+}
+
+/// A `ClientBuilder` can be used to create a blocking [`Client`] with custom configuration.
+///
+/// It mirrors the asynchronous [`ClientBuilder`](crate::ClientBuilder).
+#[must_use]
+#[derive(Debug, Default)]
+pub struct ClientBuilder {
+    inner: crate::ClientBuilder,
+}
+
+impl ClientBuilder {
+    /// Constructs a new `ClientBuilder`.
+    ///
+    /// This is the same as [`Client::builder()`].
+    pub fn new() -> Self {
+        Self {
+            inner: crate::ClientBuilder::new(),
+        }
+    }
+
+    /// Returns a [`Client`] that uses this `ClientBuilder` configuration.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if one of the supplied options was invalid, or if the
+    /// internal runtime cannot be created.
+    pub fn build(self) -> Result<Client> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("http-unix-client-blocking")
+            .enable_all()
+            .build()
+            .map_err(BuilderError::Runtime)?;
+
+        Ok(Client {
+            inner: self.inner.build()?,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Sets the `User-Agent` header to be used by this client.
+    pub fn user_agent<V>(self, value: V) -> Self
+    where
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.with_inner(|inner| inner.user_agent(value))
+    }
+
+    /// Sets the default headers for every request.
+    pub fn default_headers(self, headers: HeaderMap) -> Self {
+        self.with_inner(|inner| inner.default_headers(headers))
+    }
+
+    /// Enables a total request timeout.
+    ///
+    /// Default is no timeout.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.with_inner(|inner| inner.timeout(timeout))
+    }
+
+    /// Enables a read timeout.
+    ///
+    /// Default is no timeout.
+    pub fn read_timeout(self, timeout: Duration) -> Self {
+        self.with_inner(|inner| inner.read_timeout(timeout))
+    }
+
+    /// Set a timeout for only the connect phase of a `Client`.
+    ///
+    /// Default is no timeout.
+    pub fn connect_timeout(self, timeout: Duration) -> Self {
+        self.with_inner(|inner| inner.connect_timeout(timeout))
+    }
+
+    /// Only send requests to a server running as the user `uid`.
+    pub fn require_peer_uid(self, uid: u32) -> Self {
+        self.with_inner(|inner| inner.require_peer_uid(uid))
+    }
+
+    /// Verify the credentials of the server before sending it any request.
+    ///
+    /// Default is no verification.
+    pub fn peer_verifier<F>(self, verify: F) -> Self
+    where
+        F: Fn(&crate::PeerCredentials) -> bool + Send + Sync + 'static,
+    {
+        self.with_inner(|inner| inner.peer_verifier(verify))
+    }
+
+    /// Sets the retry policy of every request.
+    ///
+    /// Default is [`retry::Policy::none()`](crate::retry::Policy::none).
+    pub fn retry(self, policy: crate::retry::Policy) -> Self {
+        self.with_inner(|inner| inner.retry(policy))
+    }
+
+    /// Sets the redirect policy of this client.
+    ///
+    /// Default will follow redirects up to a maximum of 10.
+    pub fn redirect(self, policy: crate::redirect::Policy) -> Self {
+        self.with_inner(|inner| inner.redirect(policy))
+    }
+
+    /// Adds a [`Middleware`](crate::middleware::Middleware) around every
+    /// request of this client.
+    ///
+    /// Middlewares run in the order they were added, on the runtime of the
+    /// client.
+    pub fn middleware<M>(self, middleware: M) -> Self
+    where
+        M: crate::middleware::Middleware,
+    {
+        self.with_inner(|inner| inner.middleware(middleware))
+    }
+
+    /// Sends every request through `service`, instead of the connection pool
+    /// of the client.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `tower` feature to be enabled.
+    #[cfg(feature = "tower")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+    pub fn transport<S, B>(self, service: S) -> Self
+    where
+        S: tower_service::Service<http::Request<crate::Body>, Response = http::Response<B>>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+        S::Future: Send + 'static,
+        B: http_body::Body<Data = bytes::Bytes> + Send + Sync + 'static,
+        B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.with_inner(|inner| inner.transport(service))
+    }
+
+    /// Enable a persistent cookie store for the client.
+    ///
+    /// Default is no cookie store.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `cookies` feature to be enabled.
+    #[cfg(feature = "cookies")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cookies")))]
+    pub fn cookie_store(self, enable: bool) -> Self {
+        self.with_inner(|inner| inner.cookie_store(enable))
+    }
+
+    /// Set the persistent cookie store for the client.
+    ///
+    /// Default is no cookie store.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `cookies` feature to be enabled.
+    #[cfg(feature = "cookies")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cookies")))]
+    pub fn cookie_provider<C>(self, cookie_store: Arc<C>) -> Self
+    where
+        C: crate::cookie::CookieStore + 'static,
+    {
+        self.with_inner(|inner| inner.cookie_provider(cookie_store))
+    }
+
+    /// Enable auto gzip decompression by checking the `Content-Encoding` response header.
+    ///
+    /// Default is enabled.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `gzip` feature to be enabled.
+    #[cfg(feature = "gzip")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "gzip")))]
+    pub fn gzip(self, enable: bool) -> Self {
+        self.with_inner(|inner| inner.gzip(enable))
+    }
+
+    /// Enable auto deflate decompression by checking the `Content-Encoding` response header.
+    ///
+    /// Default is enabled.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `deflate` feature to be enabled.
+    #[cfg(feature = "deflate")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "deflate")))]
+    pub fn deflate(self, enable: bool) -> Self {
+        self.with_inner(|inner| inner.deflate(enable))
+    }
+
+    /// Enable auto brotli decompression by checking the `Content-Encoding` response header.
+    ///
+    /// Default is enabled.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `brotli` feature to be enabled.
+    #[cfg(feature = "brotli")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "brotli")))]
+    pub fn brotli(self, enable: bool) -> Self {
+        self.with_inner(|inner| inner.brotli(enable))
+    }
+
+    /// Enable auto zstd decompression by checking the `Content-Encoding` response header.
+    ///
+    /// Default is enabled.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `zstd` feature to be enabled.
+    #[cfg(feature = "zstd")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "zstd")))]
+    pub fn zstd(self, enable: bool) -> Self {
+        self.with_inner(|inner| inner.zstd(enable))
+    }
+
+    /// Sets an optional timeout for idle sockets being kept-alive.
+    ///
+    /// Pass `None` to disable the timeout. Default is 90 seconds.
+    pub fn pool_idle_timeout<D>(self, val: D) -> Self
+    where
+        D: Into<Option<Duration>>,
+    {
+        self.with_inner(|inner| inner.pool_idle_timeout(val))
+    }
+
+    /// Sets the maximum idle connections kept per socket.
+    pub fn pool_max_idle_per_socket(self, max: usize) -> Self {
+        self.with_inner(|inner| inner.pool_max_idle_per_socket(max))
+    }
+
+    /// Send headers as title case instead of lowercase.
+    pub fn http1_title_case_headers(self) -> Self {
+        self.with_inner(|inner| inner.http1_title_case_headers())
+    }
+
+    /// Sets the maximum buffer size for the HTTP/1 connection.
+    pub fn http1_max_buf_size(self, max: usize) -> Self {
+        self.with_inner(|inner| inner.http1_max_buf_size(max))
+    }
+
+    /// Allow HTTP/0.9 responses.
+    pub fn http09_responses(self) -> Self {
+        self.with_inner(|inner| inner.http09_responses())
+    }
+
+    /// Only use HTTP/2, assuming the server supports it (h2c).
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `http2` feature to be enabled.
+    #[cfg(feature = "http2")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "http2")))]
+    pub fn http2_prior_knowledge(self) -> Self {
+        self.with_inner(|inner| inner.http2_prior_knowledge())
+    }
+
+    fn with_inner<F>(mut self, func: F) -> Self
+    where
+        F: FnOnce(crate::ClientBuilder) -> crate::ClientBuilder,
+    {
+        self.inner = func(self.inner);
+        self
+    }
+}
+
+impl Client {
+    /// Creates a new blocking [`Client`] instance.
+    ///
+    /// Use [`Client::builder()`] if you wish to configure the client.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the internal runtime cannot be created, or if
+    /// called from within an async runtime.
+    pub fn new() -> Self {
+        ClientBuilder::new().build().expect("Client::new()")
+    }
+
+    /// Creates a [`ClientBuilder`] to configure a [`Client`].
+    ///
+    /// This is the same as [`ClientBuilder::new()`].
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// Start building a Request with the Method and Url.
+    ///
+    /// Returns a RequestBuilder, which will allow setting headers and the request body before sending.
+    pub fn request<P>(&self, method: Method, socket: P, path: &str) -> RequestBuilder
+    where
+        P: AsRef<Path>,
+    {
+        RequestBuilder::new(self.clone(), self.inner.request(method, socket, path))
+    }
+
+    /// Start building a Request with the Method and a [`UnixUrl`](crate::UnixUrl),
+    /// or a string in one of the formats it parses.
+    pub fn request_url<U>(&self, method: Method, url: U) -> RequestBuilder
+    where
+        U: crate::IntoUnixUrl,
+    {
+        RequestBuilder::new(self.clone(), self.inner.request_url(method, url))
+    }
+
+    /// Creates a new HTTP GET request for the given socket and path.
+    pub fn get<P>(&self, socket: P, path: &str) -> RequestBuilder
+    where
+        P: AsRef<Path>,
+    {
+        self.request(Method::GET, socket, path)
+    }
+
+    /// Creates a new HTTP POST request for the given socket and path.
+    pub fn post<P>(&self, socket: P, path: &str) -> RequestBuilder
+    where
+        P: AsRef<Path>,
+    {
+        self.request(Method::POST, socket, path)
+    }
+
+    /// Creates a new HTTP PUT request for the given socket and path.
+    pub fn put<P>(&self, socket: P, path: &str) -> RequestBuilder
+    where
+        P: AsRef<Path>,
+    {
+        self.request(Method::PUT, socket, path)
+    }
+
+    /// Creates a new HTTP PATCH request for the given socket and path.
+    pub fn patch<P>(&self, socket: P, path: &str) -> RequestBuilder
+    where
+        P: AsRef<Path>,
+    {
+        self.request(Method::PATCH, socket, path)
+    }
+
+    /// Creates a new HTTP DELETE request for the given socket and path.
+    pub fn delete<P>(&self, socket: P, path: &str) -> RequestBuilder
+    where
+        P: AsRef<Path>,
+    {
+        self.request(Method::DELETE, socket, path)
+    }
+
+    /// Creates a new HTTP HEAD request for the given socket and path.
+    pub fn head<P>(&self, socket: P, path: &str) -> RequestBuilder
+    where
+        P: AsRef<Path>,
+    {
+        self.request(Method::HEAD, socket, path)
+    }
+
+    /// Executes a [`Request`], blocking until the response headers arrive.
+    ///
+    /// You should prefer to use the `RequestBuilder` and
+    /// `RequestBuilder::send()`.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if there was an error while sending request,
+    /// or if the configured timeout elapsed before the response arrived.
+    pub fn execute(&self, request: Request) -> Result<Response> {
+        let response = self.runtime.block_on(self.inner.execute(request))?;
+
+        Ok(Response::new(response, self.runtime.clone()))
+    }
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
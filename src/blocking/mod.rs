@@ -0,0 +1,75 @@
+//! A blocking Client API.
+//!
+//! The blocking [`Client`] will block the current thread to execute, instead
+//! of returning futures that need to be executed on a runtime.
+//!
+//! Conversely, the functionality in `http_unix_client::blocking` must *not* be
+//! executed within an async runtime, or it will panic when attempting to block.
+//!
+//! # Optional
+//!
+//! This requires the optional `blocking` feature to be enabled.
+//!
+//! # Making a GET request
+//!
+//! For a single request, you can use the [`get`] shortcut method.
+//!
+//! ```rust
+//! # use http_unix_client::Error;
+//! #
+//! # fn run() -> Result<(), Error> {
+//! let body = http_unix_client::blocking::get("/tmp/my.socket", "/health")?
+//!     .text()?;
+//!
+//! println!("body = {body:?}");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Additionally, the blocking [`Response`] struct implements Rust's
+//! `Read` trait, so many useful standard library and third party crates will
+//! have convenience methods that take a `Response` anywhere `T: Read` is
+//! acceptable.
+//!
+//! **NOTE**: If you plan to perform multiple requests, it is best to create a
+//! [`Client`] and reuse it, taking advantage of keep-alive connection pooling.
+
+mod client;
+mod request;
+mod response;
+
+pub use self::client::{Client, ClientBuilder};
+pub use self::request::RequestBuilder;
+pub use self::response::Response;
+
+/// Shortcut method to quickly make a *blocking* `GET` request.
+///
+/// **NOTE**: This function creates a new internal `Client` on each call,
+/// and so should not be used if making many requests. Create a
+/// [`Client`] instead.
+///
+/// # Examples
+///
+/// ```rust
+/// # use http_unix_client::Error;
+///
+/// # fn run() -> Result<(), Error> {
+/// let body = http_unix_client::blocking::get("/tmp/my.socket", "/")?
+///     .text()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// This function fails if:
+///
+/// - supplied `path` cannot be parsed to an url
+/// - the internal runtime cannot be created
+/// - there was an error while sending request
+pub fn get<P>(socket: P, path: &str) -> crate::Result<Response>
+where
+    P: AsRef<std::path::Path>,
+{
+    Client::builder().build()?.get(socket, path).send()
+}
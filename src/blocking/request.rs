@@ -0,0 +1,206 @@
+use core::fmt;
+use serde::Serialize;
+use std::time::Duration;
+
+use super::{Client, Response};
+use crate::{
+    Body, Request, Result, Version,
+    header::{HeaderMap, HeaderName, HeaderValue},
+};
+
+/// A builder to construct the properties of a `Request`.
+///
+/// It mirrors the asynchronous [`RequestBuilder`](crate::RequestBuilder), but
+/// [`RequestBuilder::send`] blocks until the response headers arrive.
+///
+/// To construct a `RequestBuilder`, refer to the [`Client`] documentation.
+#[must_use = "RequestBuilder does nothing until you 'send' it"]
+#[derive(Debug)]
+pub struct RequestBuilder {
+    client: Client,
+    inner: crate::RequestBuilder,
+}
+
+impl RequestBuilder {
+    pub(super) fn new(client: Client, inner: crate::RequestBuilder) -> Self {
+        Self { client, inner }
+    }
+
+    /// Assemble a builder starting from an existing [`Client`] and a [`Request`].
+    pub fn from_parts(client: Client, request: Request) -> RequestBuilder {
+        let inner = crate::RequestBuilder::from_parts(client.inner.clone(), request);
+        RequestBuilder { client, inner }
+    }
+
+    /// Adds a `Header` to the request.
+    pub fn header<K, V>(self, key: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.with_inner(|inner| inner.header(key, value))
+    }
+
+    /// Add a set of Headers to the existing ones on this Request.
+    ///
+    /// The headers will be merged in to any already set.
+    pub fn headers<M>(self, headers: M) -> Self
+    where
+        HeaderMap: TryFrom<M>,
+        <HeaderMap as TryFrom<M>>::Error: Into<http::Error>,
+    {
+        self.with_inner(|inner| inner.headers(headers))
+    }
+
+    /// Enable HTTP basic authentication.
+    pub fn basic_auth<U, P>(self, username: U, password: Option<P>) -> Self
+    where
+        U: fmt::Display,
+        P: fmt::Display,
+    {
+        self.with_inner(|inner| inner.basic_auth(username, password))
+    }
+
+    /// Enable HTTP bearer authentication.
+    pub fn bearer_auth<T>(self, token: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        self.with_inner(|inner| inner.bearer_auth(token))
+    }
+
+    /// Sets the request body.
+    pub fn body<T>(self, body: T) -> Self
+    where
+        T: Into<Body>,
+    {
+        self.with_inner(|inner| inner.body(body))
+    }
+
+    /// Serializes the given value as query parameters and appends them to the URI.
+    pub fn query<T>(self, query: &T) -> Self
+    where
+        T: Serialize + ?Sized,
+    {
+        self.with_inner(|inner| inner.query(query))
+    }
+
+    /// Enables a request timeout.
+    ///
+    /// It overrides the timeout configured using `ClientBuilder::timeout()`.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.with_inner(|inner| inner.timeout(timeout))
+    }
+
+    /// Sets the retry policy of this request.
+    ///
+    /// It overrides the policy configured using `ClientBuilder::retry()`.
+    pub fn retry(self, policy: crate::retry::Policy) -> Self {
+        self.with_inner(|inner| inner.retry(policy))
+    }
+
+    /// Disables automatic decompression of the response body for this request.
+    pub fn no_decompress(self) -> Self {
+        self.with_inner(|inner| inner.no_decompress())
+    }
+
+    /// Sets the HTTP version for the request (e.g., `HTTP/2`).
+    pub fn version(self, version: Version) -> Self {
+        self.with_inner(|inner| inner.version(version))
+    }
+
+    /// Serializes the given value as a URL-encoded form body and sets the appropriate `Content-Type` header.
+    pub fn form<T>(self, form: &T) -> Self
+    where
+        T: Serialize + ?Sized,
+    {
+        self.with_inner(|inner| inner.form(form))
+    }
+
+    /// Sends a `multipart/form-data` body.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `multipart` feature to be enabled.
+    #[cfg(feature = "multipart")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+    pub fn multipart(self, form: crate::multipart::Form) -> Self {
+        self.with_inner(|inner| inner.multipart(form))
+    }
+
+    /// Send a JSON body.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `json` feature enabled.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn json<T>(self, json: &T) -> Self
+    where
+        T: Serialize + ?Sized,
+    {
+        self.with_inner(|inner| inner.json(json))
+    }
+
+    /// Returns the underlying request, consuming the builder.
+    ///
+    /// # Errors
+    /// Returns an error if the request is invalid.
+    pub fn build(self) -> Result<Request> {
+        self.inner.build()
+    }
+
+    /// Build a `Request`, which can be inspected, modified and executed with
+    /// `Client::execute()`.
+    ///
+    /// This is similar to [`RequestBuilder::build()`], but also returns the
+    /// embedded [`Client`].
+    pub fn build_split(self) -> (Client, Result<Request>) {
+        (self.client, self.inner.build())
+    }
+
+    /// Constructs the Request and sends it to the target URL, blocking until
+    /// the response headers arrive.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if there was an error while sending request.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http_unix_client::Error;
+    /// #
+    /// # fn run() -> Result<(), Error> {
+    /// let response = http_unix_client::blocking::Client::new()
+    ///     .get("/tmp/my.socket", "/get")
+    ///     .send()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn send(self) -> Result<Response> {
+        let (client, request) = self.build_split();
+        client.execute(request?)
+    }
+
+    /// Attempt to clone the RequestBuilder.
+    ///
+    /// `None` is returned if the RequestBuilder can not be cloned,
+    /// i.e. if the request body is a stream.
+    pub fn try_clone(&self) -> Option<RequestBuilder> {
+        self.inner.try_clone().map(|inner| RequestBuilder {
+            client: self.client.clone(),
+            inner,
+        })
+    }
+
+    fn with_inner<F>(mut self, func: F) -> Self
+    where
+        F: FnOnce(crate::RequestBuilder) -> crate::RequestBuilder,
+    {
+        self.inner = func(self.inner);
+        self
+    }
+}
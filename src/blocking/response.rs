@@ -0,0 +1,236 @@
+use bytes::{Buf, Bytes};
+use http::{HeaderMap, Version};
+#[cfg(feature = "json")]
+use serde::de::DeserializeOwned;
+use std::io::{self, Read, Write};
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+use crate::{PeerCredentials, Result, StatusCode, UnixUrl};
+
+/// A Response to a submitted `Request`.
+///
+/// It mirrors the asynchronous [`Response`](crate::Response), and implements
+/// [`Read`] over its body.
+#[derive(Debug)]
+pub struct Response {
+    inner: crate::Response,
+    buffer: Bytes,
+    runtime: Arc<Runtime>,
+}
+
+impl Response {
+    pub(super) fn new(inner: crate::Response, runtime: Arc<Runtime>) -> Self {
+        Self {
+            inner,
+            buffer: Bytes::new(),
+            runtime,
+        }
+    }
+
+    /// Get the `StatusCode` of this `Response`.
+    #[inline]
+    pub fn status(&self) -> StatusCode {
+        self.inner.status()
+    }
+
+    /// Get the HTTP `Version` of this `Response`.
+    #[inline]
+    pub fn version(&self) -> Version {
+        self.inner.version()
+    }
+
+    /// Get the `Headers` of this `Response`.
+    #[inline]
+    pub fn headers(&self) -> &HeaderMap {
+        self.inner.headers()
+    }
+
+    /// Get a mutable reference to the `Headers` of this `Response`.
+    #[inline]
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        self.inner.headers_mut()
+    }
+
+    /// Get the trailers received so far, if any.
+    ///
+    /// Trailers are sent after the body, so they are only available once the
+    /// body has been read to the end.
+    #[inline]
+    pub fn trailers(&self) -> Option<&HeaderMap> {
+        self.inner.trailers()
+    }
+
+    /// Get the content length of the response, if it is known.
+    ///
+    /// See [`crate::Response::content_length`] for the reasons it may not be known.
+    pub fn content_length(&self) -> Option<u64> {
+        self.inner.content_length()
+    }
+
+    /// Retrieve the cookies contained in the response.
+    ///
+    /// Note that invalid 'Set-Cookie' headers will be ignored.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `cookies` feature to be enabled.
+    #[cfg(feature = "cookies")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cookies")))]
+    pub fn cookies<'a>(&'a self) -> impl Iterator<Item = cookie::Cookie<'a>> + 'a {
+        self.inner.cookies()
+    }
+
+    /// Get the final `Url` of this `Response`.
+    #[inline]
+    pub fn url(&self) -> &UnixUrl {
+        self.inner.url()
+    }
+
+    /// Get the remote address used to get this `Response`.
+    pub fn remote_addr(&self) -> Option<SocketAddr> {
+        self.inner.remote_addr()
+    }
+
+    /// Get the credentials of the server process that answered this `Response`.
+    pub fn peer_credentials(&self) -> Option<PeerCredentials> {
+        self.inner.peer_credentials()
+    }
+
+    /// Get the path of the socket this `Response` was received from.
+    pub fn peer_path(&self) -> Option<&Path> {
+        self.inner.peer_path()
+    }
+
+    /// Returns a reference to the associated extensions.
+    pub fn extensions(&self) -> &http::Extensions {
+        self.inner.extensions()
+    }
+
+    /// Returns a mutable reference to the associated extensions.
+    pub fn extensions_mut(&mut self) -> &mut http::Extensions {
+        self.inner.extensions_mut()
+    }
+
+    /// Get the full response text.
+    ///
+    /// See [`crate::Response::text`] for how the body is decoded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http_unix_client::Error;
+    ///
+    /// # fn run() -> Result<(), Error> {
+    /// let content = http_unix_client::blocking::get("/tmp/my.socket", "/range/26")?
+    ///     .text()?;
+    ///
+    /// println!("text: {content:?}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn text(self) -> Result<String> {
+        self.runtime.block_on(self.inner.text())
+    }
+
+    /// Get the full response text given a specific encoding.
+    ///
+    /// See [`crate::Response::text_with_charset`] for how the body is decoded.
+    #[cfg(feature = "charset")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "charset")))]
+    pub fn text_with_charset(self, default_encoding: &str) -> Result<String> {
+        self.runtime
+            .block_on(self.inner.text_with_charset(default_encoding))
+    }
+
+    /// Try to deserialize the response body as JSON.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `json` feature enabled.
+    ///
+    /// # Errors
+    ///
+    /// This method fails whenever the response body is not in JSON format,
+    /// or it cannot be properly deserialized to target type `T`.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn json<T: DeserializeOwned>(self) -> Result<T> {
+        self.runtime.block_on(self.inner.json())
+    }
+
+    /// Get the full response body as `Bytes`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http_unix_client::Error;
+    ///
+    /// # fn run() -> Result<(), Error> {
+    /// let bytes = http_unix_client::blocking::get("/tmp/my.socket", "/pi")?
+    ///     .bytes()?;
+    ///
+    /// println!("bytes: {bytes:?}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bytes(self) -> Result<Bytes> {
+        self.runtime.block_on(self.inner.bytes())
+    }
+
+    /// Copy the response body into a writer.
+    ///
+    /// This function internally uses [`std::io::copy`] and hence will continuously read data from
+    /// the body and then write it into writer in a streaming fashion until EOF is met.
+    ///
+    /// # Errors
+    ///
+    /// On error, the body is reported as an I/O error wrapping the crate [`Error`](crate::Error).
+    pub fn copy_to<W>(&mut self, w: &mut W) -> io::Result<u64>
+    where
+        W: Write + ?Sized,
+    {
+        io::copy(self, w)
+    }
+
+    /// Turn a response into an error if the server returned an error.
+    pub fn error_for_status(self) -> Result<Self> {
+        let Self {
+            inner,
+            buffer,
+            runtime,
+        } = self;
+        Ok(Self {
+            inner: inner.error_for_status()?,
+            buffer,
+            runtime,
+        })
+    }
+
+    /// Turn a reference to a response into an error if the server returned an error.
+    pub fn error_for_status_ref(&self) -> Result<&Self> {
+        self.inner.error_for_status_ref()?;
+        Ok(self)
+    }
+}
+
+impl Read for Response {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while !self.buffer.has_remaining() {
+            match self
+                .runtime
+                .block_on(self.inner.chunk())
+                .map_err(io::Error::other)?
+            {
+                Some(chunk) => self.buffer = chunk,
+                None => return Ok(0),
+            }
+        }
+
+        let len = buf.len().min(self.buffer.len());
+        self.buffer.copy_to_slice(&mut buf[..len]);
+        Ok(len)
+    }
+}
@@ -1,59 +1,309 @@
 use bytes::Bytes;
+use futures_util::{TryStream, TryStreamExt};
+use http_body::{Frame, SizeHint};
+use http_body_util::{BodyExt, StreamBody, combinators::BoxBody};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+use std::time::Duration;
+use sync_wrapper::SyncStream;
+use tokio::io::AsyncRead;
+use tokio::time::{Instant, Sleep};
+use tokio_util::io::ReaderStream;
+
+use crate::Error;
+use crate::error::BoxError;
 
 /// Represents the body of an HTTP request.
-#[derive(Debug, Clone)]
+///
+/// A `Body` is either a fully buffered, reusable chunk of bytes, or a stream
+/// whose length may be unknown. Streamed bodies are sent using chunked
+/// transfer encoding unless their length is known upfront.
 pub struct Body {
-    /// The underlying byte buffer of the request body.
-    inner: Bytes,
+    inner: Inner,
+}
+
+enum Inner {
+    Reusable(Bytes),
+    Streaming(BoxBody<Bytes, BoxError>),
 }
 
 impl Body {
     /// Returns a reference to the raw bytes of the HTTP body.
+    ///
+    /// Returns `None` if the body is a stream.
     #[inline]
-    pub fn bytes(&self) -> &Bytes {
-        &self.inner
+    pub fn bytes(&self) -> Option<&Bytes> {
+        match &self.inner {
+            Inner::Reusable(bytes) => Some(bytes),
+            Inner::Streaming(_) => None,
+        }
+    }
+
+    /// Wrap a [`Stream`] in a `Body`.
+    ///
+    /// The stream is sent using chunked transfer encoding, as its length is unknown.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http_unix_client::Body;
+    /// let chunks: Vec<Result<_, std::io::Error>> = vec![Ok("hello"), Ok(" "), Ok("world")];
+    /// let stream = futures_util::stream::iter(chunks);
+    ///
+    /// let body = Body::wrap_stream(stream);
+    /// ```
+    ///
+    /// [`Stream`]: futures_util::Stream
+    pub fn wrap_stream<S>(stream: S) -> Body
+    where
+        S: TryStream + Send + 'static,
+        S::Error: Into<BoxError>,
+        Bytes: From<S::Ok>,
+    {
+        let body = StreamBody::new(SyncStream::new(
+            stream
+                .map_ok(|chunk| Frame::data(Bytes::from(chunk)))
+                .map_err(Into::into),
+        ));
+        Body {
+            inner: Inner::Streaming(BoxBody::new(body)),
+        }
+    }
+
+    /// Stream the contents of an [`AsyncRead`] as the `Body`.
+    ///
+    /// The reader is sent using chunked transfer encoding, as its length is unknown.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use http_unix_client::{Body, Client, Error};
+    /// #
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = tokio::fs::File::open("image.tar").await?;
+    ///
+    /// let response = Client::new()
+    ///     .post("/tmp/my.socket", "/images/load")
+    ///     .body(Body::from_async_read(file))
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_async_read<R>(reader: R) -> Body
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        Body::wrap_stream(ReaderStream::new(reader))
+    }
+
+    /// Attempt to clone the body.
+    ///
+    /// `None` is returned if the body is a stream, which cannot be replayed.
+    pub fn try_clone(&self) -> Option<Body> {
+        match &self.inner {
+            Inner::Reusable(bytes) => Some(Body::from(bytes.clone())),
+            Inner::Streaming(_) => None,
+        }
+    }
+
+    pub(crate) fn empty() -> Body {
+        Body::from(Bytes::new())
+    }
+}
+
+impl fmt::Debug for Body {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.inner {
+            Inner::Reusable(bytes) => f.debug_tuple("Body").field(bytes).finish(),
+            Inner::Streaming(_) => f.debug_tuple("Body").field(&"<stream>").finish(),
+        }
+    }
+}
+
+impl http_body::Body for Body {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        match &mut self.inner {
+            Inner::Reusable(bytes) => {
+                let chunk = std::mem::take(bytes);
+                if chunk.is_empty() {
+                    Poll::Ready(None)
+                } else {
+                    Poll::Ready(Some(Ok(Frame::data(chunk))))
+                }
+            }
+            Inner::Streaming(body) => Pin::new(body).poll_frame(cx),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match &self.inner {
+            Inner::Reusable(bytes) => bytes.is_empty(),
+            Inner::Streaming(body) => body.is_end_stream(),
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match &self.inner {
+            Inner::Reusable(bytes) => SizeHint::with_exact(bytes.len() as u64),
+            Inner::Streaming(body) => body.size_hint(),
+        }
     }
 }
 
 impl From<Bytes> for Body {
     #[inline]
     fn from(value: Bytes) -> Self {
-        Self { inner: value }
+        Self {
+            inner: Inner::Reusable(value),
+        }
     }
 }
 
 impl From<Vec<u8>> for Body {
     #[inline]
     fn from(value: Vec<u8>) -> Self {
-        Self {
-            inner: value.into(),
-        }
+        Self::from(Bytes::from(value))
     }
 }
 
 impl From<&'static [u8]> for Body {
     #[inline]
     fn from(value: &'static [u8]) -> Self {
-        Self {
-            inner: Bytes::from_static(value),
-        }
+        Self::from(Bytes::from_static(value))
     }
 }
 
 impl From<String> for Body {
     #[inline]
     fn from(value: String) -> Self {
-        Self {
-            inner: value.into(),
-        }
+        Self::from(Bytes::from(value))
     }
 }
 
 impl From<&'static str> for Body {
     #[inline]
     fn from(value: &'static str) -> Self {
+        Self::from(Bytes::from_static(value.as_bytes()))
+    }
+}
+
+/// The body of a [`Response`](crate::Response), with timeouts applied.
+pub(crate) type ResponseBody = BoxBody<Bytes, Error>;
+
+/// Wraps a received response body, applying the total deadline and the
+/// per-read timeout, if any.
+pub(crate) fn response(
+    body: ResponseBody,
+    deadline: Option<Instant>,
+    read_timeout: Option<Duration>,
+) -> ResponseBody {
+    let body = match deadline {
+        Some(deadline) => TotalTimeoutBody::new(body, deadline).boxed(),
+        None => body,
+    };
+    match read_timeout {
+        Some(timeout) => ReadTimeoutBody::new(body, timeout).boxed(),
+        None => body,
+    }
+}
+
+/// A body which fails with [`Error::Timeout`] once a deadline has passed.
+struct TotalTimeoutBody<B> {
+    inner: B,
+    timeout: Pin<Box<Sleep>>,
+}
+
+impl<B> TotalTimeoutBody<B> {
+    fn new(inner: B, deadline: Instant) -> Self {
+        Self {
+            inner,
+            timeout: Box::pin(tokio::time::sleep_until(deadline)),
+        }
+    }
+}
+
+impl<B> http_body::Body for TotalTimeoutBody<B>
+where
+    B: http_body::Body<Data = Bytes, Error = Error> + Unpin,
+{
+    type Data = Bytes;
+    type Error = Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        if self.timeout.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(Some(Err(Error::Timeout)));
+        }
+        Pin::new(&mut self.inner).poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// A body which fails with [`Error::Timeout`] if no frame arrives within
+/// `timeout` of the previous one.
+struct ReadTimeoutBody<B> {
+    inner: B,
+    timeout: Duration,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl<B> ReadTimeoutBody<B> {
+    fn new(inner: B, timeout: Duration) -> Self {
         Self {
-            inner: value.into(),
+            inner,
+            timeout,
+            sleep: None,
+        }
+    }
+}
+
+impl<B> http_body::Body for ReadTimeoutBody<B>
+where
+    B: http_body::Body<Data = Bytes, Error = Error> + Unpin,
+{
+    type Data = Bytes;
+    type Error = Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let timeout = self.timeout;
+        let sleep = self
+            .sleep
+            .get_or_insert_with(|| Box::pin(tokio::time::sleep(timeout)));
+        if sleep.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(Some(Err(Error::Timeout)));
         }
+
+        let frame = ready!(Pin::new(&mut self.inner).poll_frame(cx));
+        self.sleep = None;
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
     }
 }
@@ -1,23 +1,588 @@
-use http_body_util::Full;
-use hyper::body::Bytes;
+#[cfg(feature = "tower")]
+use futures_util::future::Either;
+use http::header::{ACCEPT_ENCODING, Entry, USER_AGENT};
+#[cfg(feature = "cookies")]
+use http::header::{COOKIE, SET_COOKIE};
+use http_body_util::BodyExt;
 use hyper_util::client::legacy::Client as HyperClient;
-use hyperlocal::{UnixClientExt, UnixConnector};
+use hyper_util::rt::{TokioExecutor, TokioTimer};
 use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
 
-use crate::{Error, Method, Request, RequestBuilder, Response, UnixUrl};
+#[cfg(feature = "tower")]
+use crate::service::Transport;
+#[cfg(feature = "tracing")]
+use crate::trace;
+use crate::{
+    Body, Error, Method, PeerCredentials, Request, RequestBuilder, Response, Result, SocketClient,
+    UnixUrl,
+    body::{self, ResponseBody},
+    connect::{Connector, PeerVerifier},
+    decoder::{self, Accepts},
+    error::BuilderError,
+    header::{HeaderMap, HeaderValue},
+    middleware::{Middleware, Next},
+    redirect::{self, ActionKind},
+    retry,
+    unix_url::IntoUnixUrl,
+};
 
 /// An asynchronous `Client` to make Requests over Unix socket with.
+///
+/// The `Client` holds a connection pool internally, so it is advised that
+/// you create one and **reuse** it. Cloning a `Client` is cheap, as the
+/// pool and configuration are shared behind an `Arc`.
+///
+/// To configure a `Client`, use [`Client::builder()`].
 #[derive(Debug, Clone)]
 pub struct Client {
-    inner: HyperClient<UnixConnector, Full<Bytes>>,
+    inner: Arc<ClientRef>,
 }
 
-impl Client {
-    /// Creates a new [`Client`] instance for making HTTP requests over Unix sockets.
+#[derive(Debug)]
+struct ClientRef {
+    hyper: HyperClient<Connector, Body>,
+    #[cfg(feature = "http2")]
+    hyper_h2: HyperClient<Connector, Body>,
+    headers: HeaderMap,
+    timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    accepts: Accepts,
+    retry: retry::Policy,
+    redirect: redirect::Policy,
+    #[cfg(feature = "cookies")]
+    cookie_store: Option<Arc<dyn crate::cookie::CookieStore>>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    #[cfg(feature = "tower")]
+    transport: Option<Transport>,
+}
+
+/// A `ClientBuilder` can be used to create a [`Client`] with custom configuration.
+///
+/// # Example
+///
+/// ```
+/// # use http_unix_client::{Client, Error};
+/// # use std::time::Duration;
+/// #
+/// # fn run() -> Result<(), Error> {
+/// let client = Client::builder()
+///     .user_agent("my-daemon-client/1.0")
+///     .pool_idle_timeout(Duration::from_secs(30))
+///     .pool_max_idle_per_socket(4)
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+#[derive(Debug)]
+pub struct ClientBuilder {
+    config: Config,
+}
+
+#[derive(Debug)]
+struct Config {
+    error: Option<Error>,
+    headers: HeaderMap,
+    connect_timeout: Option<Duration>,
+    peer_verifier: Option<PeerVerifier>,
+    timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    pool_idle_timeout: Option<Duration>,
+    pool_max_idle_per_socket: usize,
+    http1_title_case_headers: bool,
+    http1_max_buf_size: Option<usize>,
+    http09_responses: bool,
+    #[cfg(feature = "http2")]
+    http2_prior_knowledge: bool,
+    accepts: Accepts,
+    retry: retry::Policy,
+    redirect: redirect::Policy,
+    #[cfg(feature = "cookies")]
+    cookie_store: Option<Arc<dyn crate::cookie::CookieStore>>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    #[cfg(feature = "tower")]
+    transport: Option<Transport>,
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClientBuilder {
+    /// Constructs a new `ClientBuilder`.
+    ///
+    /// This is the same as [`Client::builder()`].
     pub fn new() -> Self {
         Self {
-            inner: HyperClient::unix(),
+            config: Config {
+                error: None,
+                headers: HeaderMap::new(),
+                connect_timeout: None,
+                peer_verifier: None,
+                timeout: None,
+                read_timeout: None,
+                pool_idle_timeout: Some(Duration::from_secs(90)),
+                pool_max_idle_per_socket: usize::MAX,
+                http1_title_case_headers: false,
+                http1_max_buf_size: None,
+                http09_responses: false,
+                #[cfg(feature = "http2")]
+                http2_prior_knowledge: false,
+                accepts: Accepts::all(),
+                retry: retry::Policy::none(),
+                redirect: redirect::Policy::default(),
+                #[cfg(feature = "cookies")]
+                cookie_store: None,
+                middlewares: Vec::new(),
+                #[cfg(feature = "tower")]
+                transport: None,
+            },
+        }
+    }
+
+    /// Returns a [`Client`] that uses this `ClientBuilder` configuration.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if one of the supplied options was invalid, for
+    /// example a `User-Agent` that is not a valid header value.
+    pub fn build(self) -> Result<Client> {
+        let config = self.config;
+
+        if let Some(err) = config.error {
+            return Err(err);
+        }
+
+        let mut builder = HyperClient::builder(TokioExecutor::new());
+        builder
+            .pool_timer(TokioTimer::new())
+            .pool_idle_timeout(config.pool_idle_timeout)
+            .pool_max_idle_per_host(config.pool_max_idle_per_socket)
+            .http1_title_case_headers(config.http1_title_case_headers)
+            .http09_responses(config.http09_responses);
+        if let Some(max) = config.http1_max_buf_size {
+            // hyper asserts this minimum, instead of returning an error.
+            if max < 8192 {
+                return Err(BuilderError::Http1MaxBufSize(max).into());
+            }
+            builder.http1_max_buf_size(max);
+        }
+        let connector = Connector::new(config.connect_timeout, config.peer_verifier);
+
+        // Requests for `HTTP/2` use a separate pool of connections speaking
+        // HTTP/2 with prior knowledge, unless every connection already does.
+        #[cfg(feature = "http2")]
+        let (hyper, hyper_h2) = {
+            let mut h2_builder = builder.clone();
+            h2_builder.http2_only(true);
+            let hyper_h2 = h2_builder.build(connector.clone());
+            if config.http2_prior_knowledge {
+                (hyper_h2.clone(), hyper_h2)
+            } else {
+                (builder.build(connector), hyper_h2)
+            }
+        };
+        #[cfg(not(feature = "http2"))]
+        let hyper = builder.build(connector);
+
+        Ok(Client {
+            inner: Arc::new(ClientRef {
+                hyper,
+                #[cfg(feature = "http2")]
+                hyper_h2,
+                headers: config.headers,
+                timeout: config.timeout,
+                read_timeout: config.read_timeout,
+                accepts: config.accepts,
+                retry: config.retry,
+                redirect: config.redirect,
+                #[cfg(feature = "cookies")]
+                cookie_store: config.cookie_store,
+                middlewares: config.middlewares,
+                #[cfg(feature = "tower")]
+                transport: config.transport,
+            }),
+        })
+    }
+
+    /// Sets the `User-Agent` header to be used by this client.
+    pub fn user_agent<V>(mut self, value: V) -> Self
+    where
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        match <HeaderValue as TryFrom<V>>::try_from(value) {
+            Ok(value) => {
+                self.config.headers.insert(USER_AGENT, value);
+            }
+            Err(err) => {
+                self.config.error = Some(BuilderError::Http(err.into()).into());
+            }
+        }
+        self
+    }
+
+    /// Sets the default headers for every request.
+    ///
+    /// Headers set on an individual request take precedence over these.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http_unix_client::{Client, Error, header};
+    /// #
+    /// # fn run() -> Result<(), Error> {
+    /// let mut headers = header::HeaderMap::new();
+    /// headers.insert("x-api-version", header::HeaderValue::from_static("2"));
+    ///
+    /// let client = Client::builder()
+    ///     .default_headers(headers)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        for (key, value) in headers.iter() {
+            self.config.headers.insert(key, value.clone());
         }
+        self
+    }
+
+    /// Enables a total request timeout.
+    ///
+    /// The timeout is applied from when the request starts connecting until the
+    /// response body has finished, across retries and redirects. Individual
+    /// requests may override it with `RequestBuilder::timeout()`.
+    ///
+    /// Default is no timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables a read timeout.
+    ///
+    /// The timeout applies to each read of the response body, and resets after
+    /// every successful read.
+    ///
+    /// Default is no timeout.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.config.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Set a timeout for only the connect phase of a `Client`.
+    ///
+    /// Default is no timeout.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Only send requests to a server running as the user `uid`.
+    ///
+    /// This is a shortcut for a [`ClientBuilder::peer_verifier`] comparing the
+    /// user ID of the server, and replaces any verifier set before.
+    pub fn require_peer_uid(self, uid: u32) -> Self {
+        self.peer_verifier(move |credentials| credentials.uid() == uid)
+    }
+
+    /// Verify the credentials of the server before sending it any request.
+    ///
+    /// The credentials are read with `SO_PEERCRED` on every new connection,
+    /// before any bytes are written to it. If `verify` returns `false`, or if
+    /// the credentials cannot be read, the request fails with
+    /// [`Error::PeerVerification`].
+    ///
+    /// Default is no verification.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http_unix_client::{Client, Error};
+    /// #
+    /// # fn run() -> Result<(), Error> {
+    /// // Only talk to a daemon running as root or in group 999.
+    /// let client = Client::builder()
+    ///     .peer_verifier(|peer| peer.uid() == 0 || peer.gid() == 999)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn peer_verifier<F>(mut self, verify: F) -> Self
+    where
+        F: Fn(&PeerCredentials) -> bool + Send + Sync + 'static,
+    {
+        self.config.peer_verifier = Some(PeerVerifier::new(verify));
+        self
+    }
+
+    /// Sets the retry policy of every request.
+    ///
+    /// Individual requests may override it with `RequestBuilder::retry()`.
+    ///
+    /// Default is [`retry::Policy::none()`].
+    pub fn retry(mut self, policy: retry::Policy) -> Self {
+        self.config.retry = policy;
+        self
+    }
+
+    /// Sets the redirect policy of this client.
+    ///
+    /// Default will follow redirects up to a maximum of 10.
+    pub fn redirect(mut self, policy: redirect::Policy) -> Self {
+        self.config.redirect = policy;
+        self
+    }
+
+    /// Adds a [`Middleware`] around every request of this client.
+    ///
+    /// Middlewares run in the order they were added, so the first one sees
+    /// the request first and the response last.
+    pub fn middleware<M>(mut self, middleware: M) -> Self
+    where
+        M: Middleware,
+    {
+        self.config.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// Sends every request through `service`, instead of the connection pool
+    /// of the client.
+    ///
+    /// The `service` may be any stack of `tower` middleware, ending with a
+    /// service which sends the request, such as another `Client`. It
+    /// receives requests with the `unix://` URL of the socket, once the
+    /// default headers, cookies and `Accept-Encoding` were added, and is
+    /// cloned for every request. Timeouts, retries, redirects and response
+    /// decompression still apply.
+    ///
+    /// The connection settings of this builder, such as the pool, connect
+    /// timeout and peer verification, are not used by `service`.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `tower` feature to be enabled.
+    #[cfg(feature = "tower")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+    pub fn transport<S, B>(mut self, service: S) -> Self
+    where
+        S: tower_service::Service<http::Request<Body>, Response = http::Response<B>>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+        S::Future: Send + 'static,
+        B: http_body::Body<Data = bytes::Bytes> + Send + Sync + 'static,
+        B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.config.transport = Some(Transport::new(service));
+        self
+    }
+
+    /// Enable a persistent cookie store for the client.
+    ///
+    /// Cookies received in responses will be preserved and included in
+    /// additional requests to the same socket.
+    ///
+    /// By default, no cookie store is used. Enabling the cookie store with
+    /// `cookie_store(true)` will set the store to a default implementation.
+    /// Calling it again with `false` removes any store, including one set
+    /// with [`ClientBuilder::cookie_provider`].
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `cookies` feature to be enabled.
+    #[cfg(feature = "cookies")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cookies")))]
+    pub fn cookie_store(mut self, enable: bool) -> Self {
+        self.config.cookie_store = if enable {
+            Some(Arc::new(crate::cookie::Jar::default()))
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Set the persistent cookie store for the client.
+    ///
+    /// Cookies received in responses will be passed to this store, and
+    /// additional requests will query this store for cookies.
+    ///
+    /// By default, no cookie store is used.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `cookies` feature to be enabled.
+    #[cfg(feature = "cookies")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cookies")))]
+    pub fn cookie_provider<C>(mut self, cookie_store: Arc<C>) -> Self
+    where
+        C: crate::cookie::CookieStore + 'static,
+    {
+        self.config.cookie_store = Some(cookie_store as _);
+        self
+    }
+
+    /// Enable auto gzip decompression by checking the `Content-Encoding` response header.
+    ///
+    /// If auto gzip decompression is turned on:
+    ///
+    /// - When sending a request and if the request's headers do not already contain
+    ///   an `Accept-Encoding` header, then `Accept-Encoding: gzip` is added.
+    /// - When receiving a response, if its headers contain a `Content-Encoding` value of
+    ///   `gzip`, both `Content-Encoding` and `Content-Length` are removed from the
+    ///   headers' set. The response body is automatically decompressed.
+    ///
+    /// Default is enabled.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `gzip` feature to be enabled.
+    #[cfg(feature = "gzip")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "gzip")))]
+    pub fn gzip(mut self, enable: bool) -> Self {
+        self.config.accepts.gzip = enable;
+        self
+    }
+
+    /// Enable auto deflate decompression by checking the `Content-Encoding` response header.
+    ///
+    /// This works like `ClientBuilder::gzip()`, for the `deflate` encoding.
+    ///
+    /// Default is enabled.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `deflate` feature to be enabled.
+    #[cfg(feature = "deflate")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "deflate")))]
+    pub fn deflate(mut self, enable: bool) -> Self {
+        self.config.accepts.deflate = enable;
+        self
+    }
+
+    /// Enable auto brotli decompression by checking the `Content-Encoding` response header.
+    ///
+    /// This works like `ClientBuilder::gzip()`, for the `br` encoding.
+    ///
+    /// Default is enabled.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `brotli` feature to be enabled.
+    #[cfg(feature = "brotli")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "brotli")))]
+    pub fn brotli(mut self, enable: bool) -> Self {
+        self.config.accepts.brotli = enable;
+        self
+    }
+
+    /// Enable auto zstd decompression by checking the `Content-Encoding` response header.
+    ///
+    /// This works like `ClientBuilder::gzip()`, for the `zstd` encoding.
+    ///
+    /// Default is enabled.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `zstd` feature to be enabled.
+    #[cfg(feature = "zstd")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "zstd")))]
+    pub fn zstd(mut self, enable: bool) -> Self {
+        self.config.accepts.zstd = enable;
+        self
+    }
+
+    /// Sets an optional timeout for idle sockets being kept-alive.
+    ///
+    /// Pass `None` to disable the timeout. Default is 90 seconds.
+    pub fn pool_idle_timeout<D>(mut self, val: D) -> Self
+    where
+        D: Into<Option<Duration>>,
+    {
+        self.config.pool_idle_timeout = val.into();
+        self
+    }
+
+    /// Sets the maximum idle connections kept per socket.
+    pub fn pool_max_idle_per_socket(mut self, max: usize) -> Self {
+        self.config.pool_max_idle_per_socket = max;
+        self
+    }
+
+    /// Send headers as title case instead of lowercase.
+    pub fn http1_title_case_headers(mut self) -> Self {
+        self.config.http1_title_case_headers = true;
+        self
+    }
+
+    /// Sets the maximum buffer size for the HTTP/1 connection.
+    ///
+    /// Default is ~400kb.
+    ///
+    /// [`ClientBuilder::build()`] fails if the value is less than 8192.
+    pub fn http1_max_buf_size(mut self, max: usize) -> Self {
+        self.config.http1_max_buf_size = Some(max);
+        self
+    }
+
+    /// Allow HTTP/0.9 responses.
+    pub fn http09_responses(mut self) -> Self {
+        self.config.http09_responses = true;
+        self
+    }
+
+    /// Only use HTTP/2, assuming the server supports it (h2c).
+    ///
+    /// Without this option, only requests whose version is set to
+    /// `Version::HTTP_2` are sent over HTTP/2.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `http2` feature to be enabled.
+    #[cfg(feature = "http2")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "http2")))]
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.config.http2_prior_knowledge = true;
+        self
+    }
+}
+
+impl Client {
+    /// Creates a new [`Client`] instance for making HTTP requests over Unix sockets.
+    ///
+    /// Use [`Client::builder()`] if you wish to configure the client.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the default configuration cannot be built.
+    pub fn new() -> Self {
+        ClientBuilder::new().build().expect("Client::new()")
+    }
+
+    /// Creates a [`ClientBuilder`] to configure a [`Client`].
+    ///
+    /// This is the same as [`ClientBuilder::new()`].
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// Binds this client to a single Unix socket.
+    ///
+    /// The returned [`SocketClient`] shares this client's connection pool and
+    /// configuration, and only takes the request path on each call.
+    pub fn for_socket<P>(&self, socket: P) -> SocketClient
+    where
+        P: AsRef<Path>,
+    {
+        SocketClient::new(self.clone(), socket)
     }
 
     /// Start building a Request with the Method and Url.
@@ -38,6 +603,38 @@ impl Client {
         RequestBuilder::new(self.clone(), req)
     }
 
+    /// Start building a Request with the Method and a [`UnixUrl`], or a string
+    /// in one of the formats parsed by `UnixUrl::from_str()`.
+    ///
+    /// # Errors
+    ///
+    /// This method fails whenever the supplied URL cannot be parsed to a [`UnixUrl`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http_unix_client::{Client, Error, Method};
+    /// #
+    /// # async fn run() -> Result<(), Error> {
+    /// let host = std::env::var("DOCKER_HOST")
+    ///     .unwrap_or_else(|_| "unix:///var/run/docker.sock".to_string());
+    ///
+    /// let version = Client::new()
+    ///     .request_url(Method::GET, &host)
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn request_url<U>(&self, method: Method, url: U) -> RequestBuilder
+    where
+        U: IntoUnixUrl,
+    {
+        let req = url.into_unix_url().map(|url| Request::new(method, url));
+
+        RequestBuilder::new(self.clone(), req)
+    }
+
     /// Creates a new HTTP GET request for the given socket and path.
     pub fn get<P>(&self, socket: P, path: &str) -> RequestBuilder
     where
@@ -94,15 +691,141 @@ impl Client {
     /// You should prefer to use the `RequestBuilder` and
     /// `RequestBuilder::send()`.
     ///
+    /// Redirects are followed according to the redirect policy of the client,
+    /// and each request is retried according to its retry policy, or the one
+    /// of the client. The middlewares of the client run around all of it.
+    ///
+    /// With the optional `tracing` feature, the execution runs in a `request`
+    /// span recording the method, socket, path, status and duration, with
+    /// events for new and reused connections and for reading the body.
+    /// Sensitive header values are redacted.
+    ///
     /// # Errors
     ///
     /// This method fails if there was an error while sending request,
-    pub async fn execute(&self, request: Request) -> Result<Response, Error> {
-        let (method, url, headers, body, version, extensions) = request.pieces();
-        let body = match body {
-            Some(body) => Full::new(body.bytes().clone()),
-            None => Full::new(Bytes::new()),
+    /// if a redirect could not be followed, if the configured timeout
+    /// elapsed before the response arrived, or if a middleware failed.
+    pub async fn execute(&self, request: Request) -> Result<Response> {
+        #[cfg(feature = "tracing")]
+        let span = trace::request_span(&request);
+        let execute = Next::new(self, &self.inner.middlewares).run(request);
+        #[cfg(feature = "tracing")]
+        let execute = trace::instrument(execute, span);
+        execute.await
+    }
+
+    pub(crate) async fn execute_without_middleware(
+        &self,
+        mut request: Request,
+    ) -> Result<Response> {
+        let policy = &self.inner.redirect;
+        let mut previous = Vec::new();
+        let deadline = request
+            .timeout()
+            .copied()
+            .or(self.inner.timeout)
+            .map(|timeout| Instant::now() + timeout);
+
+        loop {
+            let next = if policy.is_none() {
+                None
+            } else {
+                request.try_clone()
+            };
+            let response = self.execute_with_retry(request, deadline).await?;
+
+            let Some(mut next) = next else {
+                return Ok(response);
+            };
+            let url = match policy.location(&response) {
+                Some(Ok(url)) => url,
+                Some(Err(err)) => return Err(Error::Redirect(err)),
+                None => return Ok(response),
+            };
+
+            previous.push(response.url().clone());
+            match policy.check(response.status(), &url, &previous) {
+                ActionKind::Follow => {}
+                ActionKind::Stop => return Ok(response),
+                ActionKind::Error(err) => return Err(Error::Redirect(err)),
+            }
+
+            redirect::follow(&mut next, response.status(), url);
+            request = next;
+        }
+    }
+
+    async fn execute_with_retry(
+        &self,
+        mut request: Request,
+        deadline: Option<Instant>,
+    ) -> Result<Response> {
+        let policy = request.retry().unwrap_or(&self.inner.retry).clone();
+        let mut attempt = 1;
+
+        loop {
+            let retry = if policy.has_attempts_after(attempt) {
+                request.try_clone()
+            } else {
+                None
+            };
+            let method = request.method().clone();
+            let result = self.execute_once(request, deadline).await;
+
+            let Some(retry) = retry else {
+                return result;
+            };
+            let delay = match &result {
+                Ok(response) => policy.on_response(&method, response, attempt),
+                Err(err) if err.is_connect() => policy.on_connect_error(attempt),
+                Err(_) => None,
+            };
+            let Some(delay) = delay else {
+                return result;
+            };
+
+            drop(result);
+            let sleep = tokio::time::sleep(delay);
+            match deadline {
+                Some(deadline) => tokio::time::timeout_at(deadline, sleep)
+                    .await
+                    .map_err(|_| Error::Timeout)?,
+                None => sleep.await,
+            }
+            request = retry;
+            attempt += 1;
+        }
+    }
+
+    async fn execute_once(&self, request: Request, deadline: Option<Instant>) -> Result<Response> {
+        let (method, url, mut headers, body, version, extensions, decompress) = request.pieces();
+        for (key, value) in &self.inner.headers {
+            if let Entry::Vacant(entry) = headers.entry(key) {
+                entry.insert(value.clone());
+            }
+        }
+        let accepts = if decompress {
+            self.inner.accepts
+        } else {
+            Accepts::none()
         };
+        if let Some(value) = accepts.as_header_value() {
+            if let Entry::Vacant(entry) = headers.entry(ACCEPT_ENCODING) {
+                entry.insert(value);
+            }
+        }
+        #[cfg(feature = "cookies")]
+        if let Some(cookie_store) = &self.inner.cookie_store {
+            if let Entry::Vacant(entry) = headers.entry(COOKIE) {
+                if let Some(mut value) = cookie_store.cookies(&url) {
+                    value.set_sensitive(true);
+                    entry.insert(value);
+                }
+            }
+        }
+        #[cfg(feature = "tracing")]
+        trace::sending(&headers);
+        let body = body.unwrap_or_else(Body::empty);
         let mut builder = http::Request::builder()
             .method(method)
             .uri(url.clone())
@@ -114,10 +837,48 @@ impl Client {
             builder_extensions.extend(extensions);
         }
         let req = builder.body(body)?;
-        let resp = self.inner.request(req).await?;
+
+        #[cfg(feature = "tower")]
+        let pending = match &self.inner.transport {
+            Some(transport) => Either::Left(transport.call(req)),
+            None => Either::Right(self.send_pooled(req)),
+        };
+        #[cfg(not(feature = "tower"))]
+        let pending = self.send_pooled(req);
+        let resp = match deadline {
+            Some(deadline) => tokio::time::timeout_at(deadline, pending)
+                .await
+                .map_err(|_| Error::Timeout)??,
+            None => pending.await?,
+        };
+        #[cfg(feature = "tracing")]
+        trace::received(&resp);
+        #[cfg(feature = "cookies")]
+        if let Some(cookie_store) = &self.inner.cookie_store {
+            let mut cookies = resp.headers().get_all(SET_COOKIE).iter();
+            cookie_store.set_cookies(&mut cookies, &url);
+        }
+        let resp = resp.map(|body| body::response(body, deadline, self.inner.read_timeout));
+        #[cfg(feature = "tracing")]
+        let resp = resp.map(trace::body);
+        let resp = decoder::decode(resp, accepts);
 
         Ok(Response::new(resp, url))
     }
+
+    /// Sends a request over a connection of the pool of this client.
+    async fn send_pooled(&self, req: http::Request<Body>) -> Result<http::Response<ResponseBody>> {
+        #[cfg(feature = "http2")]
+        let hyper = if req.version() == http::Version::HTTP_2 {
+            &self.inner.hyper_h2
+        } else {
+            &self.inner.hyper
+        };
+        #[cfg(not(feature = "http2"))]
+        let hyper = &self.inner.hyper;
+        let resp = hyper.request(req).await?;
+        Ok(resp.map(|body| body.map_err(Error::from).boxed()))
+    }
 }
 
 impl Default for Client {
@@ -0,0 +1,241 @@
+use http::Uri;
+use hyper::rt::{Read, ReadBufCursor, Write};
+use hyper_util::client::legacy::connect::{Connected, Connection};
+use hyper_util::rt::TokioIo;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::net::UnixStream;
+use tower_service::Service;
+
+use crate::UnixUrl;
+use crate::error::{BoxError, PeerVerificationError, TimedOut};
+
+/// Connects to the Unix socket encoded in the host of a `unix://` [`Uri`].
+///
+/// The credentials of the peer are read with `SO_PEERCRED` once connected,
+/// checked by the [`PeerVerifier`], if any, before the connection is used,
+/// and attached to every response received over the connection.
+/// Connecting is bounded in time, if configured.
+#[derive(Debug, Clone)]
+pub(crate) struct Connector {
+    timeout: Option<Duration>,
+    verifier: Option<PeerVerifier>,
+}
+
+impl Connector {
+    pub(crate) fn new(timeout: Option<Duration>, verifier: Option<PeerVerifier>) -> Self {
+        Self { timeout, verifier }
+    }
+}
+
+/// A check of the credentials of the server, run on every new connection.
+#[derive(Clone)]
+pub(crate) struct PeerVerifier(Arc<dyn Fn(&PeerCredentials) -> bool + Send + Sync>);
+
+impl PeerVerifier {
+    pub(crate) fn new<F>(verify: F) -> Self
+    where
+        F: Fn(&PeerCredentials) -> bool + Send + Sync + 'static,
+    {
+        Self(Arc::new(verify))
+    }
+
+    /// Rejects the connection unless the credentials of the peer are known
+    /// and accepted.
+    fn verify(&self, conn: &Conn) -> Result<(), PeerVerificationError> {
+        match conn.peer.credentials {
+            Some(ref credentials) if (self.0)(credentials) => Ok(()),
+            credentials => Err(PeerVerificationError::new(credentials)),
+        }
+    }
+}
+
+impl fmt::Debug for PeerVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PeerVerifier")
+    }
+}
+
+impl Service<Uri> for Connector {
+    type Response = Conn;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, dst: Uri) -> Self::Future {
+        let timeout = self.timeout;
+        let verifier = self.verifier.clone();
+
+        Box::pin(async move {
+            let path = socket_path(&dst)?;
+            let connecting = Conn::connect(path);
+            let conn = match timeout {
+                Some(timeout) => match tokio::time::timeout(timeout, connecting).await {
+                    Ok(conn) => conn?,
+                    Err(_) => return Err(TimedOut.into()),
+                },
+                None => connecting.await?,
+            };
+            if let Some(verifier) = verifier {
+                verifier.verify(&conn)?;
+            }
+            Ok(conn)
+        })
+    }
+}
+
+/// The credentials of the process on the other end of a Unix socket.
+///
+/// They are read with `SO_PEERCRED` (or its platform equivalent) when the
+/// connection is established, so they identify the process that created
+/// the listening socket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerCredentials {
+    pid: Option<i32>,
+    uid: u32,
+    gid: u32,
+}
+
+impl PeerCredentials {
+    /// The process ID of the peer, if the platform reports it.
+    pub fn pid(&self) -> Option<i32> {
+        self.pid
+    }
+
+    /// The user ID of the peer.
+    pub fn uid(&self) -> u32 {
+        self.uid
+    }
+
+    /// The group ID of the peer.
+    pub fn gid(&self) -> u32 {
+        self.gid
+    }
+}
+
+/// Information about the peer of a connection, stored in the extensions of
+/// every response received over it.
+#[derive(Debug, Clone)]
+pub(crate) struct PeerInfo {
+    pub(crate) credentials: Option<PeerCredentials>,
+    pub(crate) path: Arc<Path>,
+    /// The number of responses received over the connection.
+    #[cfg(feature = "tracing")]
+    pub(crate) requests: Arc<std::sync::atomic::AtomicU64>,
+}
+
+/// A connection to a Unix socket.
+#[derive(Debug)]
+pub(crate) struct Conn {
+    inner: TokioIo<UnixStream>,
+    peer: PeerInfo,
+}
+
+impl Conn {
+    async fn connect(path: PathBuf) -> io::Result<Self> {
+        let stream = UnixStream::connect(&path).await.map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!("failed to connect to {}: {err}", path.display()),
+            )
+        })?;
+        let credentials = stream.peer_cred().ok().map(|cred| PeerCredentials {
+            pid: cred.pid(),
+            uid: cred.uid(),
+            gid: cred.gid(),
+        });
+        #[cfg(feature = "tracing")]
+        tracing::debug!(socket = %path.display(), peer = ?credentials, "connected");
+
+        Ok(Self {
+            inner: TokioIo::new(stream),
+            peer: PeerInfo {
+                credentials,
+                path: path.into(),
+                #[cfg(feature = "tracing")]
+                requests: Default::default(),
+            },
+        })
+    }
+}
+
+impl Connection for Conn {
+    fn connected(&self) -> Connected {
+        Connected::new().extra(self.peer.clone())
+    }
+}
+
+impl Read for Conn {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: ReadBufCursor<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl Write for Conn {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
+
+/// Decodes the socket path from the hex-encoded host of a `unix://` [`Uri`].
+///
+/// A path starting with a NUL byte names a Linux abstract socket, which
+/// [`UnixStream::connect`] connects to without going through the filesystem.
+fn socket_path(uri: &Uri) -> io::Result<PathBuf> {
+    if uri.scheme_str() != Some("unix") {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid URL, scheme must be unix",
+        ));
+    }
+    let host = uri.host().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid URL, host must be present",
+        )
+    })?;
+
+    UnixUrl::decode_socket(host).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid URL, host must be a hex-encoded path",
+        )
+    })
+}
@@ -0,0 +1,309 @@
+//! HTTP Cookies.
+//!
+//! A [`Client`](crate::Client) does not store cookies by default. Enable the
+//! default [`Jar`] with `ClientBuilder::cookie_store(true)`, or share a store
+//! with `ClientBuilder::cookie_provider()`.
+//!
+//! Cookies are scoped to the socket of the response that set them, and sent
+//! back on later requests to the same socket whose path matches the cookie
+//! path. The `Domain` attribute is ignored.
+//!
+//! # Example
+//!
+//! ```
+//! # use http_unix_client::{Client, Error, cookie::Jar};
+//! # use std::sync::Arc;
+//! #
+//! # fn run() -> Result<(), Error> {
+//! let jar = Arc::new(Jar::default());
+//! let client = Client::builder()
+//!     .cookie_provider(jar.clone())
+//!     .build()?;
+//!
+//! // Persist the cookies, to load them again with `Jar::load()`.
+//! let mut file = Vec::new();
+//! jar.save(&mut file).unwrap();
+//! # Ok(())
+//! # }
+//! ```
+
+use ::cookie::Cookie;
+use ::cookie::time::OffsetDateTime;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::sync::RwLock;
+use std::time::SystemTime;
+
+use crate::UnixUrl;
+use crate::header::HeaderValue;
+
+/// Actions for a persistent cookie store providing session support.
+pub trait CookieStore: Send + Sync {
+    /// Store a set of `Set-Cookie` header values received from `url`.
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &UnixUrl);
+
+    /// Get any `Cookie` values in the store for `url`.
+    fn cookies(&self, url: &UnixUrl) -> Option<HeaderValue>;
+}
+
+impl fmt::Debug for dyn CookieStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CookieStore")
+    }
+}
+
+/// A good default `CookieStore` implementation.
+///
+/// This is the implementation used when simply calling `cookie_store(true)`.
+/// This type is exposed to allow creating one and filling it with some
+/// existing cookies more easily, before creating a `Client`.
+#[derive(Debug, Default)]
+pub struct Jar {
+    cookies: RwLock<Vec<StoredCookie>>,
+}
+
+#[derive(Debug)]
+struct StoredCookie {
+    socket: String,
+    path: String,
+    expires: Option<SystemTime>,
+    cookie: Cookie<'static>,
+}
+
+impl StoredCookie {
+    fn is_expired(&self, now: SystemTime) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    fn matches(&self, url: &UnixUrl, now: SystemTime) -> bool {
+        url.as_url().host_str() == Some(&self.socket)
+            && path_matches(url.path(), &self.path)
+            && !self.is_expired(now)
+    }
+}
+
+impl Jar {
+    /// Add a cookie to this jar, as if it was received from `url`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http_unix_client::{UnixUrl, cookie::Jar};
+    ///
+    /// let cookie = "foo=bar; Path=/api";
+    /// let url = UnixUrl::new("/tmp/my.socket", "/api/login").unwrap();
+    ///
+    /// let jar = Jar::default();
+    /// jar.add_cookie_str(cookie, &url);
+    /// ```
+    pub fn add_cookie_str(&self, cookie: &str, url: &UnixUrl) {
+        if let Ok(cookie) = Cookie::parse(cookie.to_owned()) {
+            self.insert(cookie, url, SystemTime::now());
+        }
+    }
+
+    /// Writes the cookies of this jar which have not expired yet, one per line.
+    ///
+    /// Session cookies, without `Expires` nor `Max-Age`, are written too.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if writing to `writer` fails.
+    pub fn save<W>(&self, mut writer: W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let now = SystemTime::now();
+        for stored in self.read().iter().filter(|c| !c.is_expired(now)) {
+            let mut cookie = stored.cookie.clone();
+            cookie.set_path(stored.path.clone());
+            cookie.set_max_age(None);
+            if let Some(expires) = stored.expires {
+                cookie.set_expires(OffsetDateTime::from(expires));
+            }
+            writeln!(writer, "{} {}", stored.socket, cookie)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a jar previously written with [`Jar::save`].
+    ///
+    /// Invalid lines and expired cookies are ignored.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if reading from `reader` fails.
+    pub fn load<R>(reader: R) -> io::Result<Self>
+    where
+        R: BufRead,
+    {
+        let jar = Self::default();
+        let now = SystemTime::now();
+        for line in reader.lines() {
+            let line = line?;
+            let Some((socket, cookie)) = line.split_once(' ') else {
+                continue;
+            };
+            let (Ok(url), Ok(cookie)) = (
+                UnixUrl::from_encoded(socket, "/"),
+                Cookie::parse(cookie.to_owned()),
+            ) else {
+                continue;
+            };
+            jar.insert(cookie, &url, now);
+        }
+        Ok(jar)
+    }
+
+    fn insert(&self, cookie: Cookie<'static>, url: &UnixUrl, now: SystemTime) {
+        let Some(socket) = url.as_url().host_str() else {
+            return;
+        };
+        let path = match cookie.path() {
+            Some(path) if path.starts_with('/') => path.to_owned(),
+            _ => default_path(url.path()).to_owned(),
+        };
+        let expires = match (cookie.max_age(), cookie.expires_datetime()) {
+            (Some(max_age), _) => Some(
+                std::time::Duration::try_from(max_age)
+                    .map_or(SystemTime::UNIX_EPOCH, |max_age| now + max_age),
+            ),
+            (None, Some(expires)) => Some(expires.into()),
+            (None, None) => None,
+        };
+        let stored = StoredCookie {
+            socket: socket.to_owned(),
+            path,
+            expires,
+            cookie,
+        };
+
+        let mut cookies = self.write();
+        cookies.retain(|c| {
+            !(c.socket == stored.socket
+                && c.path == stored.path
+                && c.cookie.name() == stored.cookie.name())
+        });
+        if !stored.is_expired(now) {
+            cookies.push(stored);
+        }
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Vec<StoredCookie>> {
+        self.cookies.read().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Vec<StoredCookie>> {
+        self.cookies.write().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl CookieStore for Jar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &UnixUrl) {
+        let now = SystemTime::now();
+        for header in cookie_headers {
+            let Ok(header) = header.to_str() else {
+                continue;
+            };
+            if let Ok(cookie) = Cookie::parse(header.to_owned()) {
+                self.insert(cookie, url, now);
+            }
+        }
+    }
+
+    fn cookies(&self, url: &UnixUrl) -> Option<HeaderValue> {
+        let now = SystemTime::now();
+        let cookies = self.read();
+        let mut matching: Vec<_> = cookies.iter().filter(|c| c.matches(url, now)).collect();
+        if matching.is_empty() {
+            return None;
+        }
+        // Cookies with longer paths are listed first, as per RFC 6265.
+        matching.sort_by_key(|c| std::cmp::Reverse(c.path.len()));
+
+        let header = matching
+            .iter()
+            .map(|c| format!("{}={}", c.cookie.name(), c.cookie.value()))
+            .collect::<Vec<_>>()
+            .join("; ");
+        HeaderValue::from_str(&header).ok()
+    }
+}
+
+/// The default cookie path of a request path, as defined by RFC 6265.
+fn default_path(path: &str) -> &str {
+    match path.rfind('/') {
+        Some(0) | None => "/",
+        Some(index) => &path[..index],
+    }
+}
+
+/// Whether `request_path` path-matches `cookie_path`, as defined by RFC 6265.
+fn path_matches(request_path: &str, cookie_path: &str) -> bool {
+    match request_path.strip_prefix(cookie_path) {
+        Some(rest) => rest.is_empty() || cookie_path.ends_with('/') || rest.starts_with('/'),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn socket_url(socket: &str, path: &str) -> UnixUrl {
+        UnixUrl::new(socket, path).unwrap()
+    }
+
+    #[test]
+    fn cookies_match_socket_and_path() {
+        let jar = Jar::default();
+        let login = socket_url("/tmp/my.socket", "/api/login");
+        jar.add_cookie_str("session=1", &login);
+        jar.add_cookie_str("root=2; Path=/", &login);
+
+        let cookies = |socket, path| jar.cookies(&socket_url(socket, path));
+        assert_eq!(
+            cookies("/tmp/my.socket", "/api/items").unwrap(),
+            "session=1; root=2"
+        );
+        assert_eq!(cookies("/tmp/my.socket", "/apis").unwrap(), "root=2");
+        assert!(cookies("/tmp/other.socket", "/api/items").is_none());
+    }
+
+    #[test]
+    fn expired_cookies_are_removed() {
+        let jar = Jar::default();
+        let url = socket_url("/tmp/my.socket", "/");
+        jar.add_cookie_str("session=1", &url);
+        jar.add_cookie_str("session=2; Max-Age=0", &url);
+
+        assert!(jar.cookies(&url).is_none());
+    }
+
+    #[test]
+    fn save_and_load() {
+        let jar = Jar::default();
+        let url = socket_url("/tmp/my.socket", "/api/login");
+        jar.add_cookie_str("session=1; Max-Age=3600", &url);
+        jar.add_cookie_str("theme=dark", &url);
+
+        let mut saved = Vec::new();
+        jar.save(&mut saved).unwrap();
+        let loaded = Jar::load(saved.as_slice()).unwrap();
+
+        let url = socket_url("/tmp/my.socket", "/api");
+        assert_eq!(jar.cookies(&url), loaded.cookies(&url));
+        assert!(loaded.cookies(&url).is_some());
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(default_path("/"), "/");
+        assert_eq!(default_path("/login"), "/");
+        assert_eq!(default_path("/api/v1/login"), "/api/v1");
+        assert!(path_matches("/api", "/api"));
+        assert!(path_matches("/api/items", "/api"));
+        assert!(path_matches("/api/items", "/api/"));
+        assert!(!path_matches("/apis", "/api"));
+    }
+}
@@ -0,0 +1,200 @@
+//! Transparent decompression of response bodies.
+//!
+//! Each supported encoding is enabled by the cargo feature of the same name.
+
+#[cfg(any(
+    feature = "gzip",
+    feature = "deflate",
+    feature = "brotli",
+    feature = "zstd"
+))]
+use http::header::{CONTENT_ENCODING, CONTENT_LENGTH};
+
+use crate::body::ResponseBody;
+use crate::header::HeaderValue;
+
+/// The encodings a client accepts and decodes.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Accepts {
+    #[cfg(feature = "gzip")]
+    pub(crate) gzip: bool,
+    #[cfg(feature = "deflate")]
+    pub(crate) deflate: bool,
+    #[cfg(feature = "brotli")]
+    pub(crate) brotli: bool,
+    #[cfg(feature = "zstd")]
+    pub(crate) zstd: bool,
+}
+
+impl Accepts {
+    /// Accepts every encoding enabled at compile time.
+    pub(crate) fn all() -> Self {
+        Self {
+            #[cfg(feature = "gzip")]
+            gzip: true,
+            #[cfg(feature = "deflate")]
+            deflate: true,
+            #[cfg(feature = "brotli")]
+            brotli: true,
+            #[cfg(feature = "zstd")]
+            zstd: true,
+        }
+    }
+
+    /// Accepts no encoding, leaving response bodies untouched.
+    pub(crate) fn none() -> Self {
+        Self {
+            #[cfg(feature = "gzip")]
+            gzip: false,
+            #[cfg(feature = "deflate")]
+            deflate: false,
+            #[cfg(feature = "brotli")]
+            brotli: false,
+            #[cfg(feature = "zstd")]
+            zstd: false,
+        }
+    }
+
+    /// The value of the `Accept-Encoding` header to send, if any encoding is accepted.
+    pub(crate) fn as_header_value(&self) -> Option<HeaderValue> {
+        #[allow(unused_mut)]
+        let mut encodings: Vec<&str> = Vec::new();
+        #[cfg(feature = "gzip")]
+        if self.gzip {
+            encodings.push("gzip");
+        }
+        #[cfg(feature = "deflate")]
+        if self.deflate {
+            encodings.push("deflate");
+        }
+        #[cfg(feature = "brotli")]
+        if self.brotli {
+            encodings.push("br");
+        }
+        #[cfg(feature = "zstd")]
+        if self.zstd {
+            encodings.push("zstd");
+        }
+
+        if encodings.is_empty() {
+            None
+        } else {
+            HeaderValue::try_from(encodings.join(", ")).ok()
+        }
+    }
+}
+
+/// Decodes the body of a response according to its `Content-Encoding`.
+///
+/// When the body is decoded, the `Content-Encoding` and `Content-Length`
+/// headers are removed, as they no longer describe the body. Empty bodies,
+/// such as those of `HEAD` and `204 No Content` responses, are left as is.
+#[cfg_attr(
+    not(any(
+        feature = "gzip",
+        feature = "deflate",
+        feature = "brotli",
+        feature = "zstd"
+    )),
+    allow(unused_variables)
+)]
+pub(crate) fn decode(
+    response: http::Response<ResponseBody>,
+    accepts: Accepts,
+) -> http::Response<ResponseBody> {
+    #[cfg(any(
+        feature = "gzip",
+        feature = "deflate",
+        feature = "brotli",
+        feature = "zstd"
+    ))]
+    {
+        let encoding = response
+            .headers()
+            .get(CONTENT_ENCODING)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().to_ascii_lowercase());
+        let Some(encoding) = encoding else {
+            return response;
+        };
+        if http_body::Body::is_end_stream(response.body()) {
+            return response;
+        }
+
+        let (mut parts, body) = response.into_parts();
+        let body = match encoding.as_str() {
+            #[cfg(feature = "gzip")]
+            "gzip" | "x-gzip" if accepts.gzip => {
+                imp::decode(body, async_compression::tokio::bufread::GzipDecoder::new)
+            }
+            #[cfg(feature = "deflate")]
+            "deflate" if accepts.deflate => {
+                imp::decode(body, async_compression::tokio::bufread::ZlibDecoder::new)
+            }
+            #[cfg(feature = "brotli")]
+            "br" if accepts.brotli => {
+                imp::decode(body, async_compression::tokio::bufread::BrotliDecoder::new)
+            }
+            #[cfg(feature = "zstd")]
+            "zstd" if accepts.zstd => {
+                imp::decode(body, async_compression::tokio::bufread::ZstdDecoder::new)
+            }
+            _ => return http::Response::from_parts(parts, body),
+        };
+        parts.headers.remove(CONTENT_ENCODING);
+        parts.headers.remove(CONTENT_LENGTH);
+
+        http::Response::from_parts(parts, body)
+    }
+
+    #[cfg(not(any(
+        feature = "gzip",
+        feature = "deflate",
+        feature = "brotli",
+        feature = "zstd"
+    )))]
+    response
+}
+
+#[cfg(any(
+    feature = "gzip",
+    feature = "deflate",
+    feature = "brotli",
+    feature = "zstd"
+))]
+mod imp {
+    use futures_util::TryStreamExt;
+    use http_body::Frame;
+    use http_body_util::{BodyDataStream, BodyExt, StreamBody};
+    use std::io;
+    use tokio::io::AsyncRead;
+    use tokio_util::io::{ReaderStream, StreamReader};
+
+    use crate::Error;
+    use crate::body::ResponseBody;
+
+    type BodyReader = StreamReader<
+        futures_util::stream::MapErr<BodyDataStream<ResponseBody>, fn(Error) -> io::Error>,
+        bytes::Bytes,
+    >;
+
+    /// Pipes the body through the decoder built by `decoder`.
+    pub(super) fn decode<D, F>(body: ResponseBody, decoder: F) -> ResponseBody
+    where
+        F: FnOnce(BodyReader) -> D,
+        D: AsyncRead + Send + Sync + 'static,
+    {
+        let data = BodyDataStream::new(body).map_err(io::Error::other as fn(Error) -> io::Error);
+        let decoded = ReaderStream::new(decoder(StreamReader::new(data)))
+            .map_ok(Frame::data)
+            .map_err(into_error);
+
+        StreamBody::new(decoded).boxed()
+    }
+
+    /// Recovers the crate error wrapped into an [`io::Error`] while reading the
+    /// body, or reports a decompression failure.
+    fn into_error(err: io::Error) -> Error {
+        err.downcast::<Error>().unwrap_or_else(Error::Decompress)
+    }
+}
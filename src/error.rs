@@ -4,12 +4,14 @@
 //! All errors produced by this crate will be returned as a variant of [`Error`],  
 //! making error handling simple and consistent.
 
-use std::fmt;
+use std::{fmt, io};
 
 use http::StatusCode;
 use hyper::ext::ReasonPhrase;
 use thiserror::Error;
 
+use crate::PeerCredentials;
+
 /// A unified error type for all operations in this crate.
 ///
 /// Most methods return a [`Result<T, Error>`]. This enum wraps errors from
@@ -30,12 +32,55 @@ pub enum Error {
     #[cfg(feature = "json")]
     #[error("Error decoding response body.")]
     Decode(#[from] serde_json::Error),
+    /// An error deserializing a line of a newline-delimited JSON body (available when the `json` feature is enabled).
+    #[cfg(feature = "json")]
+    #[error("error decoding line {line} of response body: {source}")]
+    DecodeLine {
+        /// The number of the line, starting at 1.
+        line: usize,
+        /// The error from deserializing the line.
+        source: serde_json::Error,
+    },
     /// An error when constructing a URI.
     InvalidUriParts(#[from] http::uri::InvalidUriParts),
     /// An error from the legacy hyper client utility.
-    ClientError(#[from] hyper_util::client::legacy::Error),
+    #[error("{}", client_error_message(.0))]
+    ClientError(#[source] hyper_util::client::legacy::Error),
     /// Returned when the server responds with an error status code.]
     StatusError(#[from] StatusError),
+    /// An error decompressing the response body (available when one of the
+    /// `gzip`, `deflate`, `brotli` or `zstd` features is enabled).
+    #[cfg(any(
+        feature = "gzip",
+        feature = "deflate",
+        feature = "brotli",
+        feature = "zstd"
+    ))]
+    #[error("error decompressing response body: {0}")]
+    Decompress(io::Error),
+    /// Returned when a redirect could not be followed, such as a redirect to
+    /// another socket, a redirect loop, or an exhausted redirect limit.
+    #[error("error following redirect: {0}")]
+    Redirect(BoxError),
+    /// Returned when the server listening on the socket did not pass the
+    /// peer verification configured on the client.
+    #[error("{0}")]
+    PeerVerification(PeerVerificationError),
+    /// An error opening or using a WebSocket (available when the `websocket` feature is enabled).
+    #[cfg(feature = "websocket")]
+    #[error("websocket error: {0}")]
+    WebSocket(BoxError),
+    /// An error from the transport set with `ClientBuilder::transport()`
+    /// (available when the `tower` feature is enabled).
+    #[cfg(feature = "tower")]
+    #[error("transport error: {0}")]
+    Transport(BoxError),
+    /// An error returned by a [`Middleware`](crate::middleware::Middleware).
+    #[error("middleware error: {0}")]
+    Middleware(BoxError),
+    /// Returned when a request or reading its response body exceeded a configured timeout.
+    #[error("operation timed out")]
+    Timeout,
 }
 
 impl Error {
@@ -54,6 +99,59 @@ impl Error {
         matches!(self, Self::ClientError(err) if err.is_connect())
     }
 
+    /// Returns true if the error is from a redirect policy.
+    pub fn is_redirect(&self) -> bool {
+        matches!(self, Self::Redirect(..))
+    }
+
+    /// Returns true if the server did not pass the configured peer verification.
+    pub fn is_peer_verification(&self) -> bool {
+        matches!(self, Self::PeerVerification(..))
+    }
+
+    /// Returns true if the error is from opening or using a WebSocket.
+    #[cfg(feature = "websocket")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "websocket")))]
+    pub fn is_websocket(&self) -> bool {
+        matches!(self, Self::WebSocket(..))
+    }
+
+    /// Returns true if the error was returned by a middleware.
+    pub fn is_middleware(&self) -> bool {
+        matches!(self, Self::Middleware(..))
+    }
+
+    /// Creates an error to return from a [`Middleware`](crate::middleware::Middleware).
+    pub fn middleware<E>(err: E) -> Self
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        Self::Middleware(err.into())
+    }
+
+    /// Returns true if the error is related to a timeout.
+    ///
+    /// This includes connect timeouts, which are also reported by [`Error::is_connect`].
+    pub fn is_timeout(&self) -> bool {
+        if matches!(self, Self::Timeout) {
+            return true;
+        }
+
+        let mut source = std::error::Error::source(self);
+        while let Some(err) = source {
+            if err.is::<TimedOut>() {
+                return true;
+            }
+            if let Some(io) = err.downcast_ref::<io::Error>() {
+                if io.kind() == io::ErrorKind::TimedOut {
+                    return true;
+                }
+            }
+            source = err.source();
+        }
+        false
+    }
+
     /// Returns the status code, if the error was generated from a response.
     pub fn status(&self) -> Option<StatusCode> {
         match self {
@@ -63,6 +161,19 @@ impl Error {
     }
 }
 
+impl From<hyper_util::client::legacy::Error> for Error {
+    fn from(err: hyper_util::client::legacy::Error) -> Self {
+        let mut source = std::error::Error::source(&err);
+        while let Some(inner) = source {
+            if let Some(rejected) = inner.downcast_ref::<PeerVerificationError>() {
+                return Self::PeerVerification(rejected.clone());
+            }
+            source = inner.source();
+        }
+        Self::ClientError(err)
+    }
+}
+
 #[derive(Debug, Error)]
 #[error(transparent)]
 pub enum BuilderError {
@@ -70,11 +181,24 @@ pub enum BuilderError {
     UrlParse(#[from] url::ParseError),
     /// An error from the `http` crate.
     Http(#[from] http::Error),
+    /// A maximum HTTP/1 buffer size below the minimum of 8192 bytes.
+    #[error("HTTP/1 max buffer size {0} is less than the minimum of 8192")]
+    Http1MaxBufSize(usize),
+    /// A string which is not in any of the supported unix URL formats.
+    #[error("invalid unix URL `{0}`")]
+    InvalidUnixUrl(String),
+    /// A string which is not a valid MIME type (available when the `multipart` feature is enabled).
+    #[cfg(feature = "multipart")]
+    #[error("invalid MIME type: {0}")]
+    Mime(#[from] mime::FromStrError),
     /// An error from serializing URL query parameters.
     SerializeUrl(#[from] serde_urlencoded::ser::Error),
     /// An error from serializing or deserializing JSON (available when the `json` feature is enabled).
     #[cfg(feature = "json")]
     SerializeJson(#[from] serde_json::Error),
+    /// An error creating the runtime of a blocking client (available when the `blocking` feature is enabled).
+    #[cfg(feature = "blocking")]
+    Runtime(io::Error),
 }
 
 #[derive(Debug)]
@@ -114,5 +238,61 @@ impl fmt::Display for StatusError {
 
 impl std::error::Error for StatusError {}
 
+/// The error returned when the credentials of the server were rejected.
+#[derive(Debug, Clone)]
+pub struct PeerVerificationError {
+    credentials: Option<PeerCredentials>,
+}
+
+impl PeerVerificationError {
+    pub(crate) fn new(credentials: Option<PeerCredentials>) -> Self {
+        Self { credentials }
+    }
+
+    /// The credentials of the server, if they could be read.
+    pub fn credentials(&self) -> Option<PeerCredentials> {
+        self.credentials
+    }
+}
+
+impl fmt::Display for PeerVerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.credentials {
+            Some(cred) => write!(
+                f,
+                "peer verification failed (uid {}, gid {})",
+                cred.uid(),
+                cred.gid()
+            ),
+            None => f.write_str("peer verification failed (credentials unavailable)"),
+        }
+    }
+}
+
+impl std::error::Error for PeerVerificationError {}
+
+/// Formats an error of the hyper client with its cause, such as the socket
+/// which could not be connected to, which its own message leaves out.
+fn client_error_message(err: &hyper_util::client::legacy::Error) -> String {
+    match std::error::Error::source(err) {
+        Some(source) => format!("{err}: {source}"),
+        None => err.to_string(),
+    }
+}
+
+/// The error used internally to signal that a connection attempt timed out.
+#[derive(Debug)]
+pub(crate) struct TimedOut;
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("operation timed out")
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+pub(crate) type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
 /// A result type alias for this crate.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,147 @@
+use bytes::{Bytes, BytesMut};
+use futures_util::Stream;
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use crate::{Error, Result};
+
+/// A `Stream` decoding each line of a body as a JSON value.
+///
+/// Lines are split on `\n`, a trailing `\r` is ignored, and blank lines are
+/// skipped. The last line does not need to be terminated.
+pub(crate) struct JsonLines<S, T> {
+    body: S,
+    buf: BytesMut,
+    /// The length of the start of `buf` known not to contain a newline.
+    scanned: usize,
+    line: usize,
+    done: bool,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<S, T> JsonLines<S, T> {
+    pub(crate) fn new(body: S) -> Self {
+        Self {
+            body,
+            buf: BytesMut::new(),
+            scanned: 0,
+            line: 0,
+            done: false,
+            _value: PhantomData,
+        }
+    }
+}
+
+impl<S, T> JsonLines<S, T>
+where
+    T: DeserializeOwned,
+{
+    /// Takes the next complete line out of the buffer, or the rest of the
+    /// buffer once the body ended.
+    ///
+    /// Splitting a line off the buffer does not move the bytes after it.
+    fn next_line(&mut self) -> Option<BytesMut> {
+        match self.buf[self.scanned..].iter().position(|&b| b == b'\n') {
+            Some(pos) => {
+                let end = self.scanned + pos;
+                self.scanned = 0;
+                let mut line = self.buf.split_to(end + 1);
+                line.truncate(end);
+                Some(line)
+            }
+            None if self.done && !self.buf.is_empty() => {
+                self.scanned = 0;
+                Some(self.buf.split())
+            }
+            None => {
+                self.scanned = self.buf.len();
+                None
+            }
+        }
+    }
+
+    fn decode(&self, line: &[u8]) -> Option<Result<T>> {
+        if line.iter().all(u8::is_ascii_whitespace) {
+            return None;
+        }
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        Some(
+            serde_json::from_slice(line).map_err(|source| Error::DecodeLine {
+                line: self.line,
+                source,
+            }),
+        )
+    }
+}
+
+impl<S, T> Stream for JsonLines<S, T>
+where
+    S: Stream<Item = Result<Bytes>> + Unpin,
+    T: DeserializeOwned,
+{
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            while let Some(line) = self.next_line() {
+                self.line += 1;
+                if let Some(value) = self.decode(&line) {
+                    return Poll::Ready(Some(value));
+                }
+            }
+            if self.done {
+                return Poll::Ready(None);
+            }
+            match ready!(Pin::new(&mut self.body).poll_next(cx)) {
+                Some(Ok(chunk)) => self.buf.extend_from_slice(&chunk),
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => self.done = true,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::{StreamExt, stream};
+    use serde_json::Value;
+
+    use super::*;
+
+    async fn decode(chunks: &[&'static str]) -> Vec<Result<Value>> {
+        let body = stream::iter(
+            chunks
+                .iter()
+                .map(|chunk| Ok(Bytes::from_static(chunk.as_bytes()))),
+        );
+        let lines = JsonLines::<_, Value>::new(body);
+        lines.collect().await
+    }
+
+    #[actix_web::test]
+    async fn values_split_across_chunks() {
+        let values = decode(&["{\"a\":", "1}\r\n\n  \n[1,", "2]\n", "\"end\""]).await;
+        let values: Vec<_> = values.into_iter().map(Result::unwrap).collect();
+        assert_eq!(
+            values,
+            [
+                serde_json::json!({"a": 1}),
+                serde_json::json!([1, 2]),
+                serde_json::json!("end")
+            ]
+        );
+    }
+
+    #[actix_web::test]
+    async fn error_reports_line_number() {
+        let values = decode(&["1\n\n", "{oops}\n2\n"]).await;
+        assert_eq!(values.len(), 3);
+        match &values[1] {
+            Err(Error::DecodeLine { line, .. }) => assert_eq!(*line, 3),
+            other => panic!("unexpected {other:?}"),
+        }
+        assert!(values[2].is_ok());
+    }
+}
@@ -8,7 +8,8 @@
 //!
 //! An HTTP client for interacting with HTTP servers over Unix sockets.
 //! The crate mimics the architecture of the [reqwest](https://docs.rs/reqwest/latest/reqwest/) crate.
-//! The [`Client`] is asynchronous (requiring Tokio).
+//! The [`Client`] is asynchronous (requiring Tokio). An optional blocking
+//! client is available in the `blocking` module, with the `blocking` feature.
 //!
 //! ## Supported Platforms
 //!
@@ -107,22 +108,51 @@
 //! # }
 //! ```
 
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub mod blocking;
 mod body;
 mod client;
+mod connect;
+#[cfg(feature = "cookies")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cookies")))]
+pub mod cookie;
+mod decoder;
 mod error;
+#[cfg(feature = "json")]
+mod json_lines;
+pub mod middleware;
+#[cfg(feature = "multipart")]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+pub mod multipart;
+pub mod redirect;
 mod request;
 mod response;
+pub mod retry;
+#[cfg(feature = "tower")]
+mod service;
+mod socket_client;
+pub mod sse;
+#[cfg(feature = "tracing")]
+mod trace;
 mod unix_url;
+mod upgrade;
+#[cfg(feature = "websocket")]
+#[cfg_attr(docsrs, doc(cfg(feature = "websocket")))]
+pub mod websocket;
 
-pub use body::Body;
-pub use client::Client;
 #[cfg(feature = "cookies")]
-pub use cookie::Cookie;
-pub use error::{Error, Result};
+pub use ::cookie::Cookie;
+pub use body::Body;
+pub use client::{Client, ClientBuilder};
+pub use connect::PeerCredentials;
+pub use error::{Error, PeerVerificationError, Result};
 pub use http::{Extensions, Method, StatusCode, Uri, Version, header};
 pub use request::{Request, RequestBuilder};
 pub use response::Response;
-pub use unix_url::UnixUrl;
+pub use socket_client::SocketClient;
+pub use unix_url::{IntoUnixUrl, UnixUrl};
+pub use upgrade::Upgraded;
 pub use url::Url;
 
 /// Shortcut method to quickly make a `GET` request.
@@ -0,0 +1,102 @@
+//! Middleware around the requests of a [`Client`].
+//!
+//! A [`Middleware`] is registered with `ClientBuilder::middleware()` and runs
+//! on every request executed by the client, including those sent with
+//! `RequestBuilder::send()`. It receives the [`Request`] and the [`Next`]
+//! step of the chain: it may modify the request before running `next`,
+//! inspect or modify the response it returns, or answer without sending the
+//! request at all.
+//!
+//! Middlewares run in the order they were registered, around the whole
+//! execution of the request, redirects and retries included.
+//!
+//! # Example
+//!
+//! ```
+//! # use http_unix_client::{Client, Error, Request};
+//! # use http_unix_client::middleware::{BoxFuture, Middleware, Next};
+//! # use std::sync::atomic::{AtomicU64, Ordering};
+//! #
+//! #[derive(Debug, Default)]
+//! struct RequestId(AtomicU64);
+//!
+//! impl Middleware for RequestId {
+//!     fn handle<'a>(&'a self, mut request: Request, next: Next<'a>) -> BoxFuture<'a> {
+//!         let id = self.0.fetch_add(1, Ordering::Relaxed);
+//!         request.headers_mut().insert("x-request-id", id.into());
+//!         Box::pin(async move {
+//!             let response = next.run(request).await?;
+//!             println!("request {id}: {}", response.status());
+//!             Ok(response)
+//!         })
+//!     }
+//! }
+//!
+//! # fn run() -> Result<(), Error> {
+//! let client = Client::builder()
+//!     .middleware(RequestId::default())
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::{Client, Request, Response, Result};
+
+/// The future returned by a [`Middleware`].
+pub type BoxFuture<'a> = Pin<Box<dyn Future<Output = Result<Response>> + Send + 'a>>;
+
+/// A step in the execution of every request of a [`Client`].
+pub trait Middleware: Send + Sync + 'static {
+    /// Handles `request`, usually by running the `next` step of the chain.
+    ///
+    /// To answer without sending the request, return a response built with
+    /// `Response::from_http()`, and errors built with `Error::middleware()`.
+    fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> BoxFuture<'a>;
+}
+
+impl<M> Middleware for Arc<M>
+where
+    M: Middleware,
+{
+    fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> BoxFuture<'a> {
+        (**self).handle(request, next)
+    }
+}
+
+impl fmt::Debug for dyn Middleware {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Middleware")
+    }
+}
+
+/// The rest of the middleware chain, ending with sending the request.
+#[derive(Debug, Clone, Copy)]
+pub struct Next<'a> {
+    client: &'a Client,
+    middlewares: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(client: &'a Client, middlewares: &'a [Arc<dyn Middleware>]) -> Self {
+        Self {
+            client,
+            middlewares,
+        }
+    }
+
+    /// Runs the next middlewares, then sends `request`.
+    ///
+    /// A `Next` can be run more than once, for example to send the request
+    /// again after refreshing credentials.
+    pub fn run(self, request: Request) -> BoxFuture<'a> {
+        match self.middlewares.split_first() {
+            Some((middleware, rest)) => middleware.handle(request, Next::new(self.client, rest)),
+            None => Box::pin(self.client.execute_without_middleware(request)),
+        }
+    }
+}
@@ -0,0 +1,382 @@
+//! `multipart/form-data` request bodies.
+//!
+//! A [`Form`] is a list of named [`Part`]s, sent with
+//! `RequestBuilder::multipart()`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use http_unix_client::{Client, Error, multipart::{Form, Part}};
+//! #
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let form = Form::new()
+//!     .text("tag", "latest")
+//!     .part("context", Part::file("context.tar").await?.mime_str("application/x-tar")?);
+//!
+//! let response = Client::new()
+//!     .post("/tmp/my.socket", "/build")
+//!     .multipart(form)
+//!     .send()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Optional
+//!
+//! This requires the optional `multipart` feature to be enabled.
+
+use bytes::{Bytes, BytesMut};
+use futures_util::{StreamExt, stream};
+use http_body_util::BodyDataStream;
+use mime::Mime;
+use std::borrow::Cow;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::path::Path;
+
+use crate::Body;
+use crate::error::{BoxError, BuilderError};
+use crate::header::HeaderMap;
+
+/// A `multipart/form-data` request body.
+#[derive(Debug)]
+pub struct Form {
+    boundary: String,
+    parts: Vec<(Cow<'static, str>, Part)>,
+}
+
+/// A field of a multipart [`Form`].
+#[derive(Debug)]
+pub struct Part {
+    body: Body,
+    length: Option<u64>,
+    mime: Option<Mime>,
+    file_name: Option<Cow<'static, str>>,
+    headers: HeaderMap,
+}
+
+impl Default for Form {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Form {
+    /// Creates a new empty `Form`, with a random boundary.
+    pub fn new() -> Self {
+        Self {
+            boundary: gen_boundary(),
+            parts: Vec::new(),
+        }
+    }
+
+    /// Get the boundary that this form will use.
+    #[inline]
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Adds a text field.
+    pub fn text<T, U>(self, name: T, value: U) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+        U: Into<Cow<'static, str>>,
+    {
+        self.part(name, Part::text(value))
+    }
+
+    /// Adds a customized `Part`.
+    pub fn part<T>(mut self, name: T, part: Part) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.parts.push((name.into(), part));
+        self
+    }
+
+    /// The `Content-Type` of the request, with the boundary of this form.
+    pub(crate) fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+
+    /// The length of the encoded form, if the length of every part is known.
+    pub(crate) fn compute_length(&self) -> Option<u64> {
+        let mut length = 0;
+        for (name, part) in &self.parts {
+            length += self.part_headers(name, part).len() as u64 + part.length? + 2;
+        }
+        Some(length + self.closing_boundary().len() as u64)
+    }
+
+    /// Encodes the form. The body is reusable if every part is.
+    pub(crate) fn into_body(self) -> Body {
+        if self
+            .parts
+            .iter()
+            .all(|(_, part)| part.body.bytes().is_some())
+        {
+            let mut body = BytesMut::new();
+            for (name, part) in &self.parts {
+                body.extend_from_slice(&self.part_headers(name, part));
+                if let Some(bytes) = part.body.bytes() {
+                    body.extend_from_slice(bytes);
+                }
+                body.extend_from_slice(b"\r\n");
+            }
+            body.extend_from_slice(&self.closing_boundary());
+            return Body::from(body.freeze());
+        }
+
+        let headers: Vec<_> = self
+            .parts
+            .iter()
+            .map(|(name, part)| self.part_headers(name, part))
+            .collect();
+        let closing = self.closing_boundary();
+        let parts = headers
+            .into_iter()
+            .zip(self.parts.into_iter().map(|(_, part)| part.body))
+            .map(|(headers, body)| {
+                stream::once(async move { Ok::<_, BoxError>(headers) })
+                    .chain(BodyDataStream::new(body))
+                    .chain(stream::once(async { Ok(Bytes::from_static(b"\r\n")) }))
+            });
+        Body::wrap_stream(
+            stream::iter(parts.collect::<Vec<_>>())
+                .flatten()
+                .chain(stream::once(async move { Ok(closing) })),
+        )
+    }
+
+    fn part_headers(&self, name: &str, part: &Part) -> Bytes {
+        let mut headers = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"",
+            self.boundary,
+            escape_quoted(name)
+        );
+        if let Some(ref file_name) = part.file_name {
+            headers.push_str(&format!("; filename=\"{}\"", escape_quoted(file_name)));
+        }
+        headers.push_str("\r\n");
+        if let Some(ref mime) = part.mime {
+            headers.push_str(&format!("Content-Type: {mime}\r\n"));
+        }
+
+        let mut headers = headers.into_bytes();
+        for (key, value) in &part.headers {
+            headers.extend_from_slice(key.as_str().as_bytes());
+            headers.extend_from_slice(b": ");
+            headers.extend_from_slice(value.as_bytes());
+            headers.extend_from_slice(b"\r\n");
+        }
+        headers.extend_from_slice(b"\r\n");
+        headers.into()
+    }
+
+    fn closing_boundary(&self) -> Bytes {
+        Bytes::from(format!("--{}--\r\n", self.boundary))
+    }
+}
+
+impl Part {
+    fn new(body: Body, length: Option<u64>) -> Self {
+        Self {
+            body,
+            length,
+            mime: None,
+            file_name: None,
+            headers: HeaderMap::new(),
+        }
+    }
+
+    /// Makes a text parameter.
+    pub fn text<T>(value: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let body = match value.into() {
+            Cow::Borrowed(value) => Body::from(value),
+            Cow::Owned(value) => Body::from(value),
+        };
+        Self::stream(body)
+    }
+
+    /// Makes a new parameter from arbitrary bytes.
+    pub fn bytes<T>(value: T) -> Self
+    where
+        T: Into<Cow<'static, [u8]>>,
+    {
+        let body = match value.into() {
+            Cow::Borrowed(value) => Body::from(value),
+            Cow::Owned(value) => Body::from(value),
+        };
+        Self::stream(body)
+    }
+
+    /// Makes a new parameter from an arbitrary body.
+    ///
+    /// The length of a streaming body is unknown, so the request is sent
+    /// using chunked transfer encoding. Use [`Part::stream_with_length`]
+    /// if the length is known upfront.
+    pub fn stream<T>(value: T) -> Self
+    where
+        T: Into<Body>,
+    {
+        let body = value.into();
+        let length = body.bytes().map(|bytes| bytes.len() as u64);
+        Self::new(body, length)
+    }
+
+    /// Makes a new parameter from an arbitrary body, with a known length.
+    pub fn stream_with_length<T>(value: T, length: u64) -> Self
+    where
+        T: Into<Body>,
+    {
+        Self::new(value.into(), Some(length))
+    }
+
+    /// Makes a file parameter, streamed from the file at `path`.
+    ///
+    /// The file name of the part is the last component of `path`, and its
+    /// MIME type is `application/octet-stream`.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the file cannot be opened.
+    pub async fn file<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let file = tokio::fs::File::open(path).await?;
+        let length = file.metadata().await?.len();
+        let part = Self::stream_with_length(Body::from_async_read(file), length)
+            .mime(mime::APPLICATION_OCTET_STREAM);
+        Ok(match path.file_name() {
+            Some(file_name) => part.file_name(file_name.to_string_lossy().into_owned()),
+            None => part,
+        })
+    }
+
+    /// Tries to set the MIME type of this part.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if `mime` is not a valid MIME type.
+    pub fn mime_str(self, mime: &str) -> crate::Result<Self> {
+        let mime = mime.parse().map_err(BuilderError::Mime)?;
+        Ok(self.mime(mime))
+    }
+
+    /// Sets the MIME type of this part.
+    pub fn mime(mut self, mime: Mime) -> Self {
+        self.mime = Some(mime);
+        self
+    }
+
+    /// Sets the file name of this part.
+    pub fn file_name<T>(mut self, file_name: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Sets custom headers for this part.
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = headers;
+        self
+    }
+}
+
+/// Escapes a quoted parameter of `Content-Disposition`, as browsers do.
+fn escape_quoted(value: &str) -> Cow<'_, str> {
+    if !value.contains(['"', '\r', '\n']) {
+        return Cow::Borrowed(value);
+    }
+    Cow::Owned(
+        value
+            .replace('"', "%22")
+            .replace('\r', "%0D")
+            .replace('\n', "%0A"),
+    )
+}
+
+fn gen_boundary() -> String {
+    let random = || {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(0);
+        hasher.finish()
+    };
+    format!("{:016x}-{:016x}", random(), random())
+}
+
+#[cfg(test)]
+mod tests {
+    use http_body_util::BodyExt;
+
+    use super::*;
+
+    async fn encode(form: Form) -> String {
+        let body = form.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[actix_web::test]
+    async fn encode_reusable_and_streaming_parts() {
+        let form = || {
+            Form::new().text("name", "value").part(
+                "file",
+                Part::bytes(b"data".as_slice())
+                    .file_name("a \"b\".txt")
+                    .mime(mime::TEXT_PLAIN),
+            )
+        };
+        let expected = |boundary: &str| {
+            format!(
+                "--{boundary}\r\n\
+                 Content-Disposition: form-data; name=\"name\"\r\n\r\n\
+                 value\r\n\
+                 --{boundary}\r\n\
+                 Content-Disposition: form-data; name=\"file\"; filename=\"a %22b%22.txt\"\r\n\
+                 Content-Type: text/plain\r\n\r\n\
+                 data\r\n\
+                 --{boundary}--\r\n"
+            )
+        };
+
+        let reusable = form();
+        let boundary = reusable.boundary().to_owned();
+        let length = reusable.compute_length();
+        let body = encode(reusable).await;
+        assert_eq!(body, expected(&boundary));
+        assert_eq!(length, Some(body.len() as u64));
+
+        let chunks: Vec<io::Result<_>> = vec![Ok("str"), Ok("eam")];
+        let streaming = form().part(
+            "stream",
+            Part::stream(Body::wrap_stream(stream::iter(chunks))),
+        );
+        let boundary = streaming.boundary().to_owned();
+        assert_eq!(streaming.compute_length(), None);
+        let body = encode(streaming).await;
+        let expected = expected(&boundary).replace(
+            &format!("--{boundary}--\r\n"),
+            &format!(
+                "--{boundary}\r\n\
+                 Content-Disposition: form-data; name=\"stream\"\r\n\r\n\
+                 stream\r\n\
+                 --{boundary}--\r\n"
+            ),
+        );
+        assert_eq!(body, expected);
+    }
+
+    #[test]
+    fn boundaries_are_unique() {
+        assert_ne!(Form::new().boundary(), Form::new().boundary());
+    }
+}
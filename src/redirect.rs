@@ -0,0 +1,370 @@
+//! Redirect handling.
+//!
+//! By default, a [`Client`](crate::Client) follows up to 10 redirects. A
+//! different [`Policy`] can be set with `ClientBuilder::redirect()`.
+//!
+//! Only relative `Location` headers and `unix://` URLs on the same socket are
+//! followed, so a redirect never leaves the socket of the original request.
+//! `http://` URLs are refused, unless allowed with [`Policy::allow_http`], in
+//! which case their path and query are requested over the same socket.
+//!
+//! Requests with a streaming body are never redirected.
+//!
+//! # Example
+//!
+//! ```
+//! # use http_unix_client::{Client, Error, redirect};
+//! #
+//! # fn run() -> Result<(), Error> {
+//! let policy = redirect::Policy::custom(|attempt| {
+//!     if attempt.previous().len() > 5 {
+//!         attempt.error("too many redirects")
+//!     } else if attempt.url().path().starts_with("/admin") {
+//!         attempt.stop()
+//!     } else {
+//!         attempt.follow()
+//!     }
+//! });
+//!
+//! let client = Client::builder()
+//!     .redirect(policy)
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use http::header::{CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, LOCATION, TRANSFER_ENCODING};
+use std::fmt;
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::error::BoxError;
+use crate::{Method, Request, Response, StatusCode, UnixUrl};
+
+/// A type that controls the policy on how to handle the following of redirects.
+///
+/// The default value will catch redirect loops, and has a maximum of 10
+/// redirects it will follow in a chain before returning an error.
+#[derive(Clone)]
+pub struct Policy {
+    inner: PolicyKind,
+    allow_http: bool,
+}
+
+#[derive(Clone)]
+enum PolicyKind {
+    Custom(Arc<dyn Fn(Attempt<'_>) -> Action + Send + Sync>),
+    Limit(usize),
+    None,
+}
+
+impl Policy {
+    /// Create a `Policy` with a maximum number of redirects.
+    ///
+    /// An `Error` will be returned if the max is reached, or if a redirect
+    /// loop is detected.
+    pub fn limited(max: usize) -> Self {
+        Self {
+            inner: PolicyKind::Limit(max),
+            allow_http: false,
+        }
+    }
+
+    /// Create a `Policy` that does not follow any redirect.
+    pub fn none() -> Self {
+        Self {
+            inner: PolicyKind::None,
+            allow_http: false,
+        }
+    }
+
+    /// Create a custom `Policy` using the passed function.
+    ///
+    /// The function is called with an [`Attempt`] for every redirect, and
+    /// decides whether it is followed, stopped or turned into an error.
+    pub fn custom<T>(policy: T) -> Self
+    where
+        T: Fn(Attempt<'_>) -> Action + Send + Sync + 'static,
+    {
+        Self {
+            inner: PolicyKind::Custom(Arc::new(policy)),
+            allow_http: false,
+        }
+    }
+
+    /// Follow `Location` headers pointing to an `http://` URL, by requesting
+    /// its path and query over the same socket.
+    ///
+    /// Default is disabled, and such redirects return an error.
+    pub fn allow_http(mut self, enable: bool) -> Self {
+        self.allow_http = enable;
+        self
+    }
+
+    /// Whether this policy never follows redirects.
+    pub(crate) fn is_none(&self) -> bool {
+        matches!(self.inner, PolicyKind::None)
+    }
+
+    /// Resolves the `Location` of a redirect `response` against its URL.
+    pub(crate) fn location(&self, response: &Response) -> Option<Result<UnixUrl, BoxError>> {
+        if !is_redirect(response.status()) {
+            return None;
+        }
+        let location = response.headers().get(LOCATION)?;
+        Some(self.resolve(response.url(), location.to_str().ok()))
+    }
+
+    fn resolve(&self, current: &UnixUrl, location: Option<&str>) -> Result<UnixUrl, BoxError> {
+        let location = location.ok_or(RedirectError::InvalidLocation)?;
+        let next = current
+            .as_url()
+            .join(location)
+            .map_err(|_| RedirectError::InvalidLocation)?;
+
+        let followed = match next.scheme() {
+            "unix" => next.host() == current.as_url().host(),
+            "http" => self.allow_http,
+            _ => false,
+        };
+        if !followed {
+            return Err(RedirectError::Refused(next.into()).into());
+        }
+
+        let mut url = current.clone();
+        url.set_path(next.path());
+        url.set_query(next.query());
+        Ok(url)
+    }
+
+    pub(crate) fn check(
+        &self,
+        status: StatusCode,
+        next: &UnixUrl,
+        previous: &[UnixUrl],
+    ) -> ActionKind {
+        match self.inner {
+            PolicyKind::Custom(ref custom) => {
+                custom(Attempt {
+                    status,
+                    next,
+                    previous,
+                })
+                .inner
+            }
+            PolicyKind::Limit(max) => {
+                if previous.len() > max {
+                    ActionKind::Error(RedirectError::TooManyRedirects.into())
+                } else if previous.contains(next) {
+                    ActionKind::Error(RedirectError::Loop.into())
+                } else {
+                    ActionKind::Follow
+                }
+            }
+            PolicyKind::None => ActionKind::Stop,
+        }
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::limited(10)
+    }
+}
+
+impl fmt::Debug for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("Policy");
+        match self.inner {
+            PolicyKind::Custom(..) => debug.field("kind", &"Custom"),
+            PolicyKind::Limit(max) => debug.field("limit", &max),
+            PolicyKind::None => debug.field("kind", &"None"),
+        };
+        debug.field("allow_http", &self.allow_http).finish()
+    }
+}
+
+/// A type that holds information on the next request and previous requests
+/// in a redirect chain.
+#[derive(Debug)]
+pub struct Attempt<'a> {
+    status: StatusCode,
+    next: &'a UnixUrl,
+    previous: &'a [UnixUrl],
+}
+
+impl Attempt<'_> {
+    /// Get the type of redirect.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Get the next URL to redirect to.
+    pub fn url(&self) -> &UnixUrl {
+        self.next
+    }
+
+    /// Get the list of previous URLs that have already been requested in this chain.
+    pub fn previous(&self) -> &[UnixUrl] {
+        self.previous
+    }
+
+    /// Returns an action meaning the client should follow the next URL.
+    pub fn follow(self) -> Action {
+        Action {
+            inner: ActionKind::Follow,
+        }
+    }
+
+    /// Returns an action meaning the client should not follow the next URL.
+    ///
+    /// The 30x response will be returned as the `Ok` result.
+    pub fn stop(self) -> Action {
+        Action {
+            inner: ActionKind::Stop,
+        }
+    }
+
+    /// Returns an action failing the redirect with an error.
+    ///
+    /// The error will be returned as the `Err` result.
+    pub fn error<E>(self, error: E) -> Action
+    where
+        E: Into<BoxError>,
+    {
+        Action {
+            inner: ActionKind::Error(error.into()),
+        }
+    }
+}
+
+/// An action to perform when a redirect status code is found.
+#[derive(Debug)]
+pub struct Action {
+    inner: ActionKind,
+}
+
+#[derive(Debug)]
+pub(crate) enum ActionKind {
+    Follow,
+    Stop,
+    Error(BoxError),
+}
+
+/// Turns `request` into the request following a redirect with `status` to `url`.
+///
+/// `301` and `302` responses to a `POST`, and `303` responses to anything but
+/// a `HEAD`, are followed with a `GET` without body.
+pub(crate) fn follow(request: &mut Request, status: StatusCode, url: UnixUrl) {
+    *request.url_mut() = url;
+
+    let to_get = match status {
+        StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND => request.method() == Method::POST,
+        StatusCode::SEE_OTHER => request.method() != Method::HEAD,
+        _ => false,
+    };
+    if to_get {
+        *request.method_mut() = Method::GET;
+        *request.body_mut() = None;
+        for header in [
+            CONTENT_TYPE,
+            CONTENT_LENGTH,
+            CONTENT_ENCODING,
+            TRANSFER_ENCODING,
+        ] {
+            request.headers_mut().remove(header);
+        }
+    }
+}
+
+fn is_redirect(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::MOVED_PERMANENTLY
+            | StatusCode::FOUND
+            | StatusCode::SEE_OTHER
+            | StatusCode::TEMPORARY_REDIRECT
+            | StatusCode::PERMANENT_REDIRECT
+    )
+}
+
+#[derive(Debug, Error)]
+enum RedirectError {
+    #[error("too many redirects")]
+    TooManyRedirects,
+    #[error("redirect loop detected")]
+    Loop,
+    #[error("invalid `Location` header")]
+    InvalidLocation,
+    #[error("refusing to follow redirect to `{0}`")]
+    Refused(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn socket_url(path: &str) -> UnixUrl {
+        UnixUrl::new("/tmp/my.socket", path).unwrap()
+    }
+
+    #[test]
+    fn resolve_relative_and_same_socket() {
+        let policy = Policy::default();
+        let current = socket_url("/v1/items?page=2");
+
+        let next = policy.resolve(&current, Some("/v2/items/")).unwrap();
+        assert_eq!(next, socket_url("/v2/items/"));
+
+        let next = policy.resolve(&current, Some("other?page=3")).unwrap();
+        assert_eq!(next, socket_url("/v1/other?page=3"));
+
+        let location = socket_url("/v3").to_string();
+        let next = policy.resolve(&current, Some(&location)).unwrap();
+        assert_eq!(next, socket_url("/v3"));
+    }
+
+    #[test]
+    fn resolve_refuses_other_targets() {
+        let policy = Policy::default();
+        let current = socket_url("/");
+
+        let other = UnixUrl::new("/tmp/other.socket", "/").unwrap().to_string();
+        assert!(policy.resolve(&current, Some(&other)).is_err());
+        assert!(
+            policy
+                .resolve(&current, Some("http://localhost/v2"))
+                .is_err()
+        );
+        assert!(policy.resolve(&current, None).is_err());
+
+        let next = policy
+            .allow_http(true)
+            .resolve(&current, Some("http://localhost/v2?x=1"))
+            .unwrap();
+        assert_eq!(next, socket_url("/v2?x=1"));
+    }
+
+    #[test]
+    fn limited() {
+        let policy = Policy::limited(2);
+        let previous = [socket_url("/a"), socket_url("/b")];
+
+        assert!(matches!(
+            policy.check(StatusCode::FOUND, &socket_url("/c"), &previous[..1]),
+            ActionKind::Follow
+        ));
+        assert!(matches!(
+            policy.check(StatusCode::FOUND, &socket_url("/a"), &previous),
+            ActionKind::Error(..)
+        ));
+        assert!(matches!(
+            policy.check(
+                StatusCode::FOUND,
+                &socket_url("/c"),
+                &vec![socket_url("/x"); 3]
+            ),
+            ActionKind::Error(..)
+        ));
+    }
+}
@@ -1,15 +1,20 @@
 use base64::{Engine, prelude::BASE64_STANDARD};
 use core::fmt;
 use serde::Serialize;
+use std::time::Duration;
 
 use crate::{
     Body, Client, Extensions, Method, Response, Result, UnixUrl, Version,
     error::BuilderError,
-    header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue},
+    header::{
+        ACCEPT, AUTHORIZATION, CONNECTION, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue,
+        UPGRADE,
+    },
+    retry,
 };
 
 /// A request which can be executed with `Client::execute()`.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Request {
     method: Method,
     url: UnixUrl,
@@ -17,12 +22,18 @@ pub struct Request {
     body: Option<Body>,
     version: Version,
     extensions: Extensions,
+    timeout: Option<Duration>,
+    decompress: bool,
+    retry: Option<retry::Policy>,
 }
 
 impl Request {
     /// Constructs a new request.
+    ///
+    /// This allows sending a request to any [`UnixUrl`], such as one created
+    /// with `UnixUrl::abstract_socket()`, with `Client::execute()`.
     #[inline]
-    pub(super) fn new(method: Method, url: UnixUrl) -> Self {
+    pub fn new(method: Method, url: UnixUrl) -> Self {
         Request {
             method,
             url,
@@ -30,6 +41,9 @@ impl Request {
             body: None,
             version: Version::default(),
             extensions: Extensions::new(),
+            timeout: None,
+            decompress: true,
+            retry: None,
         }
     }
 
@@ -105,6 +119,45 @@ impl Request {
         &mut self.version
     }
 
+    /// Get the timeout.
+    #[inline]
+    pub fn timeout(&self) -> Option<&Duration> {
+        self.timeout.as_ref()
+    }
+
+    /// Get a mutable reference to the timeout.
+    #[inline]
+    pub fn timeout_mut(&mut self) -> &mut Option<Duration> {
+        &mut self.timeout
+    }
+
+    /// The retry policy of this request, overriding the one of the client.
+    pub(crate) fn retry(&self) -> Option<&retry::Policy> {
+        self.retry.as_ref()
+    }
+
+    /// Attempt to clone the request.
+    ///
+    /// `None` is returned if the request can not be cloned, i.e. if the body is a stream.
+    pub fn try_clone(&self) -> Option<Request> {
+        let body = match self.body.as_ref() {
+            Some(body) => Some(body.try_clone()?),
+            None => None,
+        };
+        Some(Request {
+            method: self.method.clone(),
+            url: self.url.clone(),
+            headers: self.headers.clone(),
+            body,
+            version: self.version,
+            extensions: self.extensions.clone(),
+            timeout: self.timeout,
+            decompress: self.decompress,
+            retry: self.retry.clone(),
+        })
+    }
+
+    #[allow(clippy::type_complexity)]
     pub(super) fn pieces(
         self,
     ) -> (
@@ -114,6 +167,7 @@ impl Request {
         Option<Body>,
         Version,
         Extensions,
+        bool,
     ) {
         (
             self.method,
@@ -122,6 +176,7 @@ impl Request {
             self.body,
             self.version,
             self.extensions,
+            self.decompress,
         )
     }
 }
@@ -160,6 +215,53 @@ impl RequestBuilder {
         self.header_sensitive(key, value, false)
     }
 
+    /// Ask the server to upgrade the connection to `protocol`.
+    ///
+    /// This sets the `Connection: Upgrade` and `Upgrade` headers. Once the
+    /// server answered `101 Switching Protocols`, use `Response::upgrade()`
+    /// to get the upgraded connection.
+    pub fn upgrade<V>(self, protocol: V) -> Self
+    where
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        self.header::<_, HeaderValue>(CONNECTION, HeaderValue::from_static("Upgrade"))
+            .header(UPGRADE, protocol)
+    }
+
+    /// Performs the WebSocket opening handshake, and returns the `WebSocket`.
+    ///
+    /// The handshake is a request like any other, so default headers,
+    /// authentication and the socket of this request are used.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if there was an error while sending the request, or
+    /// if the server did not accept the WebSocket handshake.
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `websocket` feature to be enabled.
+    #[cfg(feature = "websocket")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "websocket")))]
+    pub async fn websocket(self) -> Result<crate::websocket::WebSocket> {
+        crate::websocket::connect(self).await
+    }
+
+    /// Opens an [`EventSource`](crate::sse::EventSource), a `Stream` of
+    /// Server-Sent Events which sends this request again when the connection
+    /// is lost.
+    ///
+    /// The `Accept` header is set to `text/event-stream`, unless already set.
+    pub fn event_source(mut self) -> crate::sse::EventSource {
+        if let Ok(ref mut req) = self.request {
+            req.headers_mut()
+                .entry(ACCEPT)
+                .or_insert(HeaderValue::from_static("text/event-stream"));
+        }
+        crate::sse::EventSource::new(self)
+    }
+
     /// Add a `Header` to this Request with ability to define if `header_value` is sensitive.
     fn header_sensitive<K, V>(mut self, key: K, value: V, sensitive: bool) -> Self
     where
@@ -223,6 +325,9 @@ impl RequestBuilder {
 
     /// Enable HTTP basic authentication.
     ///
+    /// The credentials are sent to whichever process listens on the socket.
+    /// Use `ClientBuilder::require_peer_uid()` to check the server first.
+    ///
     /// ```
     /// # use http_unix_client::{Client, Error};
     ///
@@ -249,6 +354,9 @@ impl RequestBuilder {
     }
 
     /// Enable HTTP bearer authentication.
+    ///
+    /// Like [`RequestBuilder::basic_auth`], the token is sent to whichever
+    /// process listens on the socket.
     pub fn bearer_auth<T>(self, token: T) -> Self
     where
         T: fmt::Display,
@@ -296,7 +404,44 @@ impl RequestBuilder {
         self
     }
 
+    /// Enables a request timeout.
+    ///
+    /// The timeout is applied from when the request starts connecting until the
+    /// response body has finished, across retries and redirects. It affects
+    /// only this request and overrides the timeout configured using
+    /// `ClientBuilder::timeout()`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        if let Ok(ref mut req) = self.request {
+            *req.timeout_mut() = Some(timeout);
+        }
+        self
+    }
+
+    /// Sets the retry policy of this request.
+    ///
+    /// It overrides the policy configured using `ClientBuilder::retry()`.
+    pub fn retry(mut self, policy: retry::Policy) -> Self {
+        if let Ok(ref mut req) = self.request {
+            req.retry = Some(policy);
+        }
+        self
+    }
+
+    /// Disables automatic decompression of the response body for this request.
+    ///
+    /// No `Accept-Encoding` header is added, and a compressed response body is
+    /// returned as is, with its `Content-Encoding` header.
+    pub fn no_decompress(mut self) -> Self {
+        if let Ok(ref mut req) = self.request {
+            req.decompress = false;
+        }
+        self
+    }
+
     /// Sets the HTTP version for the request (e.g., `HTTP/2`).
+    ///
+    /// `HTTP/2` requests are sent with prior knowledge, and require the
+    /// optional `http2` feature to be enabled.
     pub fn version(mut self, version: Version) -> Self {
         if let Ok(ref mut req) = self.request {
             *req.version_mut() = version;
@@ -325,6 +470,53 @@ impl RequestBuilder {
         self
     }
 
+    /// Sends a `multipart/form-data` body.
+    ///
+    /// The `Content-Type` is set with the boundary of the form, and the
+    /// `Content-Length` if the length of every part is known.
+    ///
+    /// ```no_run
+    /// # use http_unix_client::{Client, Error, multipart::Form};
+    /// #
+    /// # async fn run() -> Result<(), Error> {
+    /// let form = Form::new()
+    ///     .text("key3", "value3")
+    ///     .text("key4", "value4");
+    ///
+    /// let response = Client::new()
+    ///     .post("/tmp/my.socket", "/upload")
+    ///     .multipart(form)
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Optional
+    ///
+    /// This requires the optional `multipart` feature to be enabled.
+    #[cfg(feature = "multipart")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+    pub fn multipart(mut self, form: crate::multipart::Form) -> Self {
+        if let Ok(ref mut req) = self.request {
+            match HeaderValue::try_from(form.content_type()) {
+                Ok(content_type) => {
+                    req.headers_mut().insert(CONTENT_TYPE, content_type);
+                }
+                Err(err) => {
+                    self.request = Err(BuilderError::Http(err.into()).into());
+                    return self;
+                }
+            }
+            if let Some(length) = form.compute_length() {
+                req.headers_mut()
+                    .insert(http::header::CONTENT_LENGTH, HeaderValue::from(length));
+            }
+            *req.body_mut() = Some(form.into_body());
+        }
+        self
+    }
+
     /// Send a JSON body.
     ///
     /// # Optional
@@ -403,6 +595,7 @@ impl RequestBuilder {
     /// Attempt to clone the RequestBuilder.
     ///
     /// `None` is returned if the RequestBuilder can not be cloned,
+    /// i.e. if the request body is a stream.
     ///
     /// # Examples
     ///
@@ -419,10 +612,14 @@ impl RequestBuilder {
     /// # }
     /// ```
     pub fn try_clone(&self) -> Option<RequestBuilder> {
-        self.request.as_ref().ok().map(|req| RequestBuilder {
-            client: self.client.clone(),
-            request: Ok(req.clone()),
-        })
+        self.request
+            .as_ref()
+            .ok()
+            .and_then(|req| req.try_clone())
+            .map(|req| RequestBuilder {
+                client: self.client.clone(),
+                request: Ok(req),
+            })
     }
 }
 
@@ -571,6 +768,16 @@ mod tests {
         assert!(req.body().is_none());
     }
 
+    #[test]
+    fn try_clone_stream() {
+        let chunks: Vec<std::io::Result<&'static str>> = vec![Ok("hello"), Ok("world")];
+        let client = Client::new();
+        let builder = client
+            .post("/tmp/my.socket", "/post")
+            .body(Body::wrap_stream(futures_util::stream::iter(chunks)));
+        assert!(builder.try_clone().is_none());
+    }
+
     #[test]
     fn test_basic_auth_sensitive_header() {
         let client = Client::new();
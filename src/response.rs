@@ -2,36 +2,60 @@ use bytes::Bytes;
 #[cfg(feature = "cookies")]
 use cookie::Cookie;
 use encoding_rs::{Encoding, UTF_8};
+use futures_util::{Stream, TryStreamExt};
 #[cfg(feature = "cookies")]
 use http::header::SET_COOKIE;
 use http::{HeaderMap, Version};
 use http_body::Body;
-use http_body_util::BodyExt;
-use hyper::{body::Incoming, ext::ReasonPhrase};
+use http_body_util::{BodyDataStream, BodyExt};
+use hyper::ext::ReasonPhrase;
 use hyper_util::client::legacy::connect::HttpInfo;
 use mime::Mime;
 #[cfg(feature = "json")]
 use serde::de::DeserializeOwned;
+use std::io;
 use std::net::SocketAddr;
+use std::path::Path;
+use tokio::io::AsyncRead;
+use tokio_util::io::StreamReader;
 
+use crate::body::ResponseBody;
+use crate::connect::{PeerCredentials, PeerInfo};
 use crate::error::StatusError;
-use crate::{Result, StatusCode, UnixUrl};
+use crate::sse::EventStream;
+use crate::{Result, StatusCode, UnixUrl, Upgraded};
 
 /// A Response to a submitted `Request`.
 #[derive(Debug)]
 pub struct Response {
-    response: http::Response<Incoming>,
+    response: http::Response<ResponseBody>,
     url: Box<UnixUrl>,
+    trailers: Option<HeaderMap>,
 }
 
 impl Response {
-    pub(super) fn new(response: http::Response<Incoming>, url: UnixUrl) -> Self {
+    pub(super) fn new(response: http::Response<ResponseBody>, url: UnixUrl) -> Self {
         Self {
             response,
             url: Box::new(url),
+            trailers: None,
         }
     }
 
+    /// Creates a `Response` from an `http::Response`, as if it was received
+    /// from `url`.
+    ///
+    /// This lets a [`Middleware`](crate::middleware::Middleware) answer a
+    /// request without sending it. Errors reading a streaming body are
+    /// reported as middleware errors.
+    pub fn from_http<T>(response: http::Response<T>, url: UnixUrl) -> Self
+    where
+        T: Into<crate::Body>,
+    {
+        let response = response.map(|body| body.into().map_err(crate::Error::Middleware).boxed());
+        Self::new(response, url)
+    }
+
     /// Get the `StatusCode` of this `Response`.
     pub fn status(&self) -> StatusCode {
         self.response.status()
@@ -55,6 +79,17 @@ impl Response {
         self.response.headers_mut()
     }
 
+    /// Get the trailers received so far, if any.
+    ///
+    /// Trailers are sent after the body, so they are only available once the
+    /// body has been read to the end with [`Response::chunk`]. The methods
+    /// consuming the response, such as [`Response::bytes`] and
+    /// [`Response::bytes_stream`], discard them.
+    #[inline]
+    pub fn trailers(&self) -> Option<&HeaderMap> {
+        self.trailers.as_ref()
+    }
+
     /// Get the content length of the response, if it is known.
     ///
     /// This value does not directly represents the value of the `Content-Length`
@@ -103,6 +138,23 @@ impl Response {
             .map(|info| info.remote_addr())
     }
 
+    /// Get the credentials of the server process that answered this `Response`.
+    ///
+    /// They are read with `SO_PEERCRED` when the connection is established,
+    /// and are `None` if the platform does not support it.
+    pub fn peer_credentials(&self) -> Option<PeerCredentials> {
+        self.peer_info().and_then(|info| info.credentials)
+    }
+
+    /// Get the path of the socket this `Response` was received from.
+    pub fn peer_path(&self) -> Option<&Path> {
+        self.peer_info().map(|info| &*info.path)
+    }
+
+    fn peer_info(&self) -> Option<&PeerInfo> {
+        self.response.extensions().get::<PeerInfo>()
+    }
+
     /// Returns a reference to the associated extensions.
     pub fn extensions(&self) -> &http::Extensions {
         self.response.extensions()
@@ -254,8 +306,50 @@ impl Response {
         Ok(json)
     }
 
+    /// Convert the response into a `Stream` of the values of a newline-delimited
+    /// JSON body, one value per line.
+    ///
+    /// Values may be split across chunks of the body, and blank lines are
+    /// skipped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use http_unix_client::Error;
+    /// use futures_util::StreamExt;
+    /// use serde_json::Value;
+    ///
+    /// # async fn run() -> Result<(), Error> {
+    /// let mut events = http_unix_client::get("/var/run/docker.sock", "/events")
+    ///     .await?
+    ///     .json_lines::<Value>();
+    ///
+    /// while let Some(event) = events.next().await {
+    ///     println!("event: {}", event?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// An item is an error if reading the body failed, or if a line cannot be
+    /// deserialized to target type `T`. In the latter case, the error is an
+    /// [`Error::DecodeLine`](crate::Error::DecodeLine) with the number of the
+    /// line, and the following lines are still decoded.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn json_lines<T: DeserializeOwned>(
+        self,
+    ) -> impl Stream<Item = crate::Result<T>> + Send + Sync + Unpin {
+        crate::json_lines::JsonLines::new(self.bytes_stream())
+    }
+
     /// Get the full response body as `Bytes`.
     ///
+    /// The trailers of the response, if any, are discarded. Use
+    /// [`Response::chunk`] to read them.
+    ///
     /// # Example
     ///
     /// ```
@@ -295,15 +389,130 @@ impl Response {
     /// # }
     /// ```
     pub async fn chunk(&mut self) -> Result<Option<Bytes>> {
-        if let Some(res) = self.response.body_mut().frame().await {
-            let frame = res?;
-            if let Ok(chunk) = frame.into_data() {
-                return Ok(Some(chunk));
+        while let Some(res) = self.response.body_mut().frame().await {
+            match res?.into_data() {
+                Ok(chunk) => return Ok(Some(chunk)),
+                Err(frame) => {
+                    if let Ok(trailers) = frame.into_trailers() {
+                        self.trailers.get_or_insert_default().extend(trailers);
+                    }
+                }
             }
         }
         Ok(None)
     }
 
+    /// Convert the response into a `Stream` of `Bytes` from the body.
+    ///
+    /// The trailers of the response, if any, are discarded. Use
+    /// [`Response::chunk`] to read them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use http_unix_client::Error;
+    /// use futures_util::StreamExt;
+    ///
+    /// # async fn run() -> Result<(), Error> {
+    /// let mut stream = http_unix_client::get("/tmp/my.socket", "/logs")
+    ///     .await?
+    ///     .bytes_stream();
+    ///
+    /// while let Some(item) = stream.next().await {
+    ///     println!("Chunk: {:?}", item?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bytes_stream(self) -> impl Stream<Item = Result<Bytes>> + Send + Sync + Unpin {
+        BodyDataStream::new(self.response.into_body())
+    }
+
+    /// Convert the response into an [`AsyncRead`] over the body.
+    ///
+    /// Errors while reading the body are reported as [`io::Error`]s wrapping
+    /// the crate [`Error`](crate::Error). Like with [`Response::bytes_stream`],
+    /// the trailers of the response are discarded.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut reader = http_unix_client::get("/tmp/my.socket", "/images/get")
+    ///     .await?
+    ///     .into_async_read();
+    /// let mut file = tokio::fs::File::create("image.tar").await?;
+    ///
+    /// tokio::io::copy(&mut reader, &mut file).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_async_read(self) -> impl AsyncRead + Send + Sync + Unpin {
+        StreamReader::new(self.bytes_stream().map_err(io::Error::other))
+    }
+
+    /// Convert the response into a `Stream` of the Server-Sent [`Event`]s of a
+    /// `text/event-stream` body.
+    ///
+    /// To reconnect when the connection is lost, use an
+    /// [`EventSource`](crate::sse::EventSource) instead.
+    ///
+    /// [`Event`]: crate::sse::Event
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use http_unix_client::Error;
+    /// use futures_util::StreamExt;
+    ///
+    /// # async fn run() -> Result<(), Error> {
+    /// let mut events = http_unix_client::get("/tmp/my.socket", "/events")
+    ///     .await?
+    ///     .events();
+    ///
+    /// while let Some(event) = events.next().await {
+    ///     println!("Event: {:?}", event?.data);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn events(self) -> EventStream {
+        EventStream::new(self)
+    }
+
+    /// Consumes the response and returns the upgraded connection, once the
+    /// server answered `101 Switching Protocols`.
+    ///
+    /// The request should be built with `RequestBuilder::upgrade()`.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the server did not upgrade the connection.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use http_unix_client::{Client, Error};
+    /// # use tokio::io::AsyncWriteExt;
+    /// #
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut stream = Client::new()
+    ///     .post("/var/run/docker.sock", "/containers/abc/attach?stream=1&stdin=1")
+    ///     .upgrade("tcp")
+    ///     .send()
+    ///     .await?
+    ///     .upgrade()
+    ///     .await?;
+    ///
+    /// stream.write_all(b"ls\n").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn upgrade(self) -> crate::Result<Upgraded> {
+        let upgraded = hyper::upgrade::on(self.response).await?;
+        Ok(Upgraded::new(upgraded))
+    }
+
     /// Turn a response into an error if the server returned an error.
     ///
     /// # Example
@@ -0,0 +1,220 @@
+//! Retry policies for failed requests.
+//!
+//! By default, a [`Client`](crate::Client) does not retry requests. A
+//! [`Policy`] can be set for every request with `ClientBuilder::retry()`, or
+//! for a single request with `RequestBuilder::retry()`.
+//!
+//! A request is only retried if its body can be replayed, i.e. if it is not a
+//! stream. Connect errors are retried for any method, as the request was never
+//! sent. Responses with a retryable status are only retried for idempotent
+//! methods.
+//!
+//! # Example
+//!
+//! ```
+//! # use http_unix_client::{Client, Error, StatusCode, retry};
+//! # use std::time::Duration;
+//! #
+//! # fn run() -> Result<(), Error> {
+//! let policy = retry::Policy::attempts(5)
+//!     .backoff(Duration::from_millis(50), Duration::from_secs(2))
+//!     .retry_on_status(StatusCode::SERVICE_UNAVAILABLE);
+//!
+//! let client = Client::builder()
+//!     .retry(policy)
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use http::header::RETRY_AFTER;
+use std::hash::{BuildHasher, RandomState};
+use std::time::{Duration, SystemTime};
+
+use crate::{Method, Response, StatusCode};
+
+/// A type that controls whether and when a failed request is retried.
+#[derive(Debug, Clone)]
+pub struct Policy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retry_on_connect: bool,
+    statuses: Vec<StatusCode>,
+    honor_retry_after: bool,
+}
+
+impl Policy {
+    /// Create a `Policy` that never retries.
+    pub fn none() -> Self {
+        Self::attempts(1)
+    }
+
+    /// Create a `Policy` making at most `max_attempts` attempts, including the first one.
+    ///
+    /// The policy retries connect errors, backs off exponentially from 100
+    /// milliseconds up to 10 seconds with jitter, and honors `Retry-After`.
+    /// No status code is retried until added with [`Policy::retry_on_status`].
+    pub fn attempts(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            jitter: true,
+            retry_on_connect: true,
+            statuses: Vec::new(),
+            honor_retry_after: true,
+        }
+    }
+
+    /// Sets the delay before the first retry, and the maximum delay between retries.
+    ///
+    /// The delay doubles after every attempt.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Randomize each delay between half and all of its exponential value.
+    ///
+    /// Default is enabled.
+    pub fn jitter(mut self, enable: bool) -> Self {
+        self.jitter = enable;
+        self
+    }
+
+    /// Retry requests which failed to connect to the socket.
+    ///
+    /// Default is enabled.
+    pub fn retry_on_connect(mut self, enable: bool) -> Self {
+        self.retry_on_connect = enable;
+        self
+    }
+
+    /// Retry idempotent requests whose response has the given status.
+    pub fn retry_on_status(mut self, status: StatusCode) -> Self {
+        if !self.statuses.contains(&status) {
+            self.statuses.push(status);
+        }
+        self
+    }
+
+    /// Wait for the delay of the `Retry-After` response header, if any, instead
+    /// of the backoff. The delay is capped at the maximum backoff.
+    ///
+    /// Default is enabled.
+    pub fn honor_retry_after(mut self, enable: bool) -> Self {
+        self.honor_retry_after = enable;
+        self
+    }
+
+    /// Whether another attempt may follow attempt number `attempt`.
+    pub(crate) fn has_attempts_after(&self, attempt: u32) -> bool {
+        attempt < self.max_attempts
+    }
+
+    /// The delay before retrying after a connect error, if it should be retried.
+    pub(crate) fn on_connect_error(&self, attempt: u32) -> Option<Duration> {
+        self.retry_on_connect.then(|| self.backoff_for(attempt))
+    }
+
+    /// The delay before retrying after `response`, if it should be retried.
+    pub(crate) fn on_response(
+        &self,
+        method: &Method,
+        response: &Response,
+        attempt: u32,
+    ) -> Option<Duration> {
+        if !method.is_idempotent() || !self.statuses.contains(&response.status()) {
+            return None;
+        }
+
+        let retry_after = if self.honor_retry_after {
+            retry_after(response).map(|delay| delay.min(self.max_backoff))
+        } else {
+            None
+        };
+        Some(retry_after.unwrap_or_else(|| self.backoff_for(attempt)))
+    }
+
+    fn backoff_for(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+
+        if self.jitter {
+            let random = RandomState::new().hash_one(attempt) as f64 / u64::MAX as f64;
+            delay.mul_f64(0.5 + random / 2.0)
+        } else {
+            delay
+        }
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Parses the `Retry-After` header, either as delay-seconds or as an HTTP-date.
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_is_exponential_and_capped() {
+        let policy = Policy::attempts(10)
+            .backoff(Duration::from_millis(100), Duration::from_secs(1))
+            .jitter(false);
+
+        assert_eq!(policy.backoff_for(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff_for(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff_for(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff_for(5), Duration::from_secs(1));
+        assert_eq!(policy.backoff_for(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn backoff_jitter_range() {
+        let policy =
+            Policy::attempts(3).backoff(Duration::from_millis(100), Duration::from_secs(1));
+
+        for _ in 0..32 {
+            let delay = policy.backoff_for(2);
+            assert!(delay >= Duration::from_millis(100));
+            assert!(delay <= Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn attempts() {
+        assert!(!Policy::none().has_attempts_after(1));
+        assert!(Policy::attempts(2).has_attempts_after(1));
+        assert!(!Policy::attempts(2).has_attempts_after(2));
+        assert!(Policy::attempts(3).on_connect_error(1).is_some());
+        assert!(
+            Policy::attempts(3)
+                .retry_on_connect(false)
+                .on_connect_error(1)
+                .is_none()
+        );
+    }
+}
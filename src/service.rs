@@ -0,0 +1,102 @@
+use bytes::Bytes;
+use http_body_util::BodyExt;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_service::Service;
+
+use crate::body::ResponseBody;
+use crate::error::BoxError;
+use crate::{Body, Client, Error, Request, Response, Result};
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = Result<T>> + Send>>;
+
+impl Service<Request> for Client {
+    type Response = Response;
+    type Error = Error;
+    type Future = BoxFuture<Response>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let client = self.clone();
+        Box::pin(async move { client.execute(request).await })
+    }
+}
+
+impl Service<Request> for &'_ Client {
+    type Response = Response;
+    type Error = Error;
+    type Future = BoxFuture<Response>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let client = self.clone();
+        Box::pin(async move { client.execute(request).await })
+    }
+}
+
+/// A `Service` sending the requests of a [`Client`] in place of its
+/// connection pool.
+#[derive(Clone)]
+pub(crate) struct Transport(
+    Arc<dyn Fn(http::Request<Body>) -> BoxFuture<http::Response<ResponseBody>> + Send + Sync>,
+);
+
+impl Transport {
+    pub(crate) fn new<S, B>(service: S) -> Self
+    where
+        S: Service<http::Request<Body>, Response = http::Response<B>>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        S::Error: Into<BoxError>,
+        S::Future: Send + 'static,
+        B: http_body::Body<Data = Bytes> + Send + Sync + 'static,
+        B::Error: Into<BoxError>,
+    {
+        Self(Arc::new(move |request| {
+            let mut service = service.clone();
+            Box::pin(async move {
+                futures_util::future::poll_fn(|cx| service.poll_ready(cx))
+                    .await
+                    .map_err(transport_error)?;
+                let response = service.call(request).await.map_err(transport_error)?;
+                Ok(response.map(|body| body.map_err(transport_error).boxed()))
+            })
+        }))
+    }
+
+    pub(crate) fn call(
+        &self,
+        request: http::Request<Body>,
+    ) -> BoxFuture<http::Response<ResponseBody>> {
+        (self.0)(request)
+    }
+}
+
+impl fmt::Debug for Transport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Transport")
+    }
+}
+
+/// Converts an error of the transport, unwrapping errors of this crate, such
+/// as those of a `Client` used as the transport of another one.
+fn transport_error<E>(err: E) -> Error
+where
+    E: Into<BoxError>,
+{
+    match err.into().downcast::<Error>() {
+        Ok(err) => *err,
+        Err(err) => Error::Transport(err),
+    }
+}
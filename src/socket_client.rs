@@ -0,0 +1,157 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::{Client, Error, Method, Request, RequestBuilder, Response, Result, UnixUrl};
+
+/// A [`Client`] bound to a single Unix socket.
+///
+/// Requests made through a `SocketClient` only take the request path, which is
+/// joined onto an optional base path. The socket path is encoded once, when the
+/// `SocketClient` is created.
+///
+/// A `SocketClient` is created with [`Client::for_socket()`]. It shares the
+/// connection pool and configuration of the [`Client`] it was created from.
+///
+/// # Example
+///
+/// ```
+/// # use http_unix_client::{Client, Error};
+/// #
+/// # async fn run() -> Result<(), Error> {
+/// let docker = Client::new()
+///     .for_socket("/var/run/docker.sock")
+///     .base_path("/v1.43");
+///
+/// // GET /v1.43/containers/json
+/// let containers = docker.get("/containers/json").send().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SocketClient {
+    client: Client,
+    encoded_socket: Arc<str>,
+    base_path: Arc<str>,
+}
+
+impl SocketClient {
+    pub(crate) fn new<P>(client: Client, socket: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        Self {
+            client,
+            encoded_socket: UnixUrl::encode_socket(socket).into(),
+            base_path: "".into(),
+        }
+    }
+
+    /// Sets a path prefix joined onto every request path, such as `/v1.43`.
+    pub fn base_path(mut self, base_path: &str) -> Self {
+        let base_path = base_path.trim_end_matches('/');
+        self.base_path = if base_path.is_empty() || base_path.starts_with('/') {
+            base_path.into()
+        } else {
+            format!("/{base_path}").into()
+        };
+        self
+    }
+
+    /// Returns the underlying [`Client`].
+    #[inline]
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Start building a Request with the Method and a path on the bound socket.
+    ///
+    /// Returns a RequestBuilder, which will allow setting headers and the request body before sending.
+    ///
+    /// # Errors
+    ///
+    /// This method fails whenever the base path and supplied path cannot parsed to a [`UnixUrl`].
+    pub fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let path = if path.starts_with('/') {
+            format!("{}{path}", self.base_path)
+        } else {
+            format!("{}/{path}", self.base_path)
+        };
+        let req = UnixUrl::from_encoded(&self.encoded_socket, &path)
+            .map(|url| Request::new(method, url))
+            .map_err(Error::from);
+
+        RequestBuilder::new(self.client.clone(), req)
+    }
+
+    /// Creates a new HTTP GET request for the given path.
+    pub fn get(&self, path: &str) -> RequestBuilder {
+        self.request(Method::GET, path)
+    }
+
+    /// Creates a new HTTP POST request for the given path.
+    pub fn post(&self, path: &str) -> RequestBuilder {
+        self.request(Method::POST, path)
+    }
+
+    /// Creates a new HTTP PUT request for the given path.
+    pub fn put(&self, path: &str) -> RequestBuilder {
+        self.request(Method::PUT, path)
+    }
+
+    /// Creates a new HTTP PATCH request for the given path.
+    pub fn patch(&self, path: &str) -> RequestBuilder {
+        self.request(Method::PATCH, path)
+    }
+
+    /// Creates a new HTTP DELETE request for the given path.
+    pub fn delete(&self, path: &str) -> RequestBuilder {
+        self.request(Method::DELETE, path)
+    }
+
+    /// Creates a new HTTP HEAD request for the given path.
+    pub fn head(&self, path: &str) -> RequestBuilder {
+        self.request(Method::HEAD, path)
+    }
+
+    /// Executes a [`Request`].
+    ///
+    /// This is the same as [`Client::execute()`]; the socket of the request is not changed.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if there was an error while sending request.
+    pub async fn execute(&self, request: Request) -> Result<Response> {
+        self.client.execute(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bound_socket_path() {
+        let client = Client::new().for_socket("/tmp/my.socket");
+        let req = client.get("/status").build().expect("request is valid");
+        assert_eq!(
+            req.url().as_str(),
+            "unix://2f746d702f6d792e736f636b6574/status"
+        );
+    }
+
+    #[test]
+    fn bound_socket_base_path() {
+        let client = Client::new()
+            .for_socket("/tmp/my.socket")
+            .base_path("v1.43/");
+        let req = client
+            .get("containers/json?all=1")
+            .build()
+            .expect("request is valid");
+        assert_eq!(req.url().path(), "/v1.43/containers/json");
+        assert_eq!(req.url().query(), Some("all=1"));
+
+        let req = client.get("/").build().expect("request is valid");
+        assert_eq!(req.url().path(), "/v1.43/");
+    }
+}
@@ -0,0 +1,352 @@
+//! Server-Sent Events.
+//!
+//! A response with a `text/event-stream` body is parsed into [`Event`]s with
+//! `Response::events()`. To keep receiving events across disconnections, open
+//! an [`EventSource`] with `RequestBuilder::event_source()`: it reconnects
+//! after the delay requested by the server, resending the ID of the last
+//! received event in the `Last-Event-ID` header.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use http_unix_client::{Client, Error};
+//! # use futures_util::StreamExt;
+//! #
+//! # async fn run() -> Result<(), Error> {
+//! let mut events = Client::new()
+//!     .get("/run/my-daemon.sock", "/events")
+//!     .event_source();
+//!
+//! while let Some(event) = events.next().await {
+//!     let event = event?;
+//!     println!("{}: {}", event.event, event.data);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use std::collections::VecDeque;
+use std::fmt;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+use std::time::Duration;
+
+use crate::{RequestBuilder, Response, Result, StatusCode};
+
+/// The reconnection delay used until the server sends a `retry` field.
+const DEFAULT_RETRY: Duration = Duration::from_secs(3);
+
+/// An event received from a `text/event-stream` body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    /// The last event ID set by the server, if any.
+    ///
+    /// Like in browsers, the ID is kept for the following events until the
+    /// server sets another one, or resets it with an empty `id` field.
+    pub id: Option<String>,
+    /// The event type, `message` if the server did not set one.
+    pub event: String,
+    /// The data of the event, its lines joined with `\n`.
+    pub data: String,
+    /// The reconnection delay requested by the server since the previous
+    /// event, if any.
+    pub retry: Option<Duration>,
+}
+
+/// A `Stream` of the [`Event`]s of a response body.
+///
+/// To create an `EventStream`, refer to `Response::events()`.
+pub struct EventStream {
+    body: Pin<Box<dyn Stream<Item = Result<Bytes>> + Send + Sync>>,
+    parser: Parser,
+}
+
+impl EventStream {
+    pub(crate) fn new(response: Response) -> Self {
+        Self {
+            body: Box::pin(response.bytes_stream()),
+            parser: Parser::default(),
+        }
+    }
+}
+
+impl fmt::Debug for EventStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventStream").finish_non_exhaustive()
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Result<Event>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(event) = self.parser.events.pop_front() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+            match ready!(self.body.as_mut().poll_next(cx)) {
+                Some(Ok(chunk)) => self.parser.feed(&chunk),
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                // An event which is not terminated by an empty line is discarded.
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// An incremental parser of the `text/event-stream` format, as specified by
+/// the HTML Living Standard.
+#[derive(Debug, Default)]
+struct Parser {
+    line: Vec<u8>,
+    after_cr: bool,
+    after_first_line: bool,
+    last_event_id: Option<String>,
+    event: String,
+    data: String,
+    retry: Option<Duration>,
+    events: VecDeque<Event>,
+}
+
+impl Parser {
+    fn feed(&mut self, chunk: &[u8]) {
+        for &byte in chunk {
+            // A CRLF may be split across two chunks.
+            if mem::take(&mut self.after_cr) && byte == b'\n' {
+                continue;
+            }
+            match byte {
+                b'\n' => self.end_line(),
+                b'\r' => {
+                    self.end_line();
+                    self.after_cr = true;
+                }
+                byte => self.line.push(byte),
+            }
+        }
+    }
+
+    fn end_line(&mut self) {
+        let mut line = mem::take(&mut self.line);
+        if !mem::replace(&mut self.after_first_line, true) && line.starts_with(b"\xEF\xBB\xBF") {
+            line.drain(..3);
+        }
+        let line = String::from_utf8_lossy(&line);
+
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (&*line, ""),
+        };
+        match field {
+            "event" => self.event = value.to_owned(),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => {
+                self.last_event_id = Some(value.to_owned()).filter(|id| !id.is_empty());
+            }
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                if let Ok(millis) = value.parse() {
+                    self.retry = Some(Duration::from_millis(millis));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn dispatch(&mut self) {
+        let event = mem::take(&mut self.event);
+        if self.data.is_empty() {
+            return;
+        }
+        let mut data = mem::take(&mut self.data);
+        data.pop();
+
+        self.events.push_back(Event {
+            id: self.last_event_id.clone(),
+            event: if event.is_empty() {
+                "message".to_owned()
+            } else {
+                event
+            },
+            data,
+            retry: self.retry.take(),
+        });
+    }
+}
+
+/// A `Stream` of [`Event`]s which reconnects when the connection is lost.
+///
+/// After a disconnection or a connection error, the request is sent again
+/// once the reconnection delay has elapsed, with a `Last-Event-ID` header
+/// if the server set an event ID. The delay is 3 seconds, until the server
+/// sets another one with a `retry` field.
+///
+/// The stream ends when the server answers `204 No Content`. Errors are
+/// yielded before reconnecting, and end the stream when the server answers
+/// with an error status or the request is invalid. Requests with a streaming
+/// body can not be sent again, so they are never reconnected.
+///
+/// To open an `EventSource`, refer to `RequestBuilder::event_source()`.
+pub struct EventSource {
+    inner: Pin<Box<dyn Stream<Item = Result<Event>> + Send>>,
+}
+
+impl EventSource {
+    pub(crate) fn new(request: RequestBuilder) -> Self {
+        let state = Reconnect {
+            request: Some(request),
+            last_event_id: None,
+            retry: DEFAULT_RETRY,
+            events: None,
+            reconnecting: false,
+        };
+        let inner = futures_util::stream::unfold(state, async |mut state| {
+            let item = state.next().await?;
+            Some((item, state))
+        });
+        Self {
+            inner: Box::pin(inner),
+        }
+    }
+}
+
+impl fmt::Debug for EventSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventSource").finish_non_exhaustive()
+    }
+}
+
+impl Stream for EventSource {
+    type Item = Result<Event>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+struct Reconnect {
+    /// The request to send on the next connection, `None` once the stream ended.
+    request: Option<RequestBuilder>,
+    last_event_id: Option<String>,
+    retry: Duration,
+    events: Option<EventStream>,
+    reconnecting: bool,
+}
+
+impl Reconnect {
+    async fn next(&mut self) -> Option<Result<Event>> {
+        loop {
+            if let Some(events) = &mut self.events {
+                match events.next().await {
+                    Some(Ok(event)) => {
+                        self.last_event_id.clone_from(&event.id);
+                        if let Some(retry) = event.retry {
+                            self.retry = retry;
+                        }
+                        return Some(Ok(event));
+                    }
+                    Some(Err(err)) => {
+                        self.events = None;
+                        return Some(Err(err));
+                    }
+                    None => self.events = None,
+                }
+            }
+
+            let mut request = self.request.take()?;
+            self.request = request.try_clone();
+            if let Some(ref id) = self.last_event_id {
+                request = request.header("last-event-id", id.as_str());
+            }
+            if mem::replace(&mut self.reconnecting, true) {
+                tokio::time::sleep(self.retry).await;
+            }
+
+            match request.send().await.and_then(Response::error_for_status) {
+                Ok(response) if response.status() == StatusCode::NO_CONTENT => {
+                    self.request = None;
+                    return None;
+                }
+                Ok(response) => {
+                    let mut events = response.events();
+                    events.parser.last_event_id.clone_from(&self.last_event_id);
+                    self.events = Some(events);
+                }
+                Err(err) => {
+                    if err.is_builder() || err.is_status() {
+                        self.request = None;
+                    }
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(chunks: &[&str]) -> Vec<Event> {
+        let mut parser = Parser::default();
+        for chunk in chunks {
+            parser.feed(chunk.as_bytes());
+        }
+        parser.events.into()
+    }
+
+    fn event(id: Option<&str>, event: &str, data: &str) -> Event {
+        Event {
+            id: id.map(str::to_owned),
+            event: event.to_owned(),
+            data: data.to_owned(),
+            retry: None,
+        }
+    }
+
+    #[test]
+    fn multi_line_data_and_comments() {
+        let events = parse(&[": keep-alive\n", "data: first\ndata:second\n\n"]);
+        assert_eq!(events, [event(None, "message", "first\nsecond")]);
+    }
+
+    #[test]
+    fn line_endings() {
+        let events = parse(&["data: a\r", "\ndata: b\r\rdata: c\r\n", "\r\n"]);
+        assert_eq!(
+            events,
+            [event(None, "message", "a\nb"), event(None, "message", "c")]
+        );
+    }
+
+    #[test]
+    fn fields() {
+        let events = parse(&[
+            "\u{FEFF}id: 1\nevent: update\nretry: 1500\ndata\n\n",
+            "retry: soon\ndata: {}\n\n",
+            "id\nevent: ignored\n\n",
+            "data: no end",
+        ]);
+        assert_eq!(
+            events,
+            [
+                Event {
+                    retry: Some(Duration::from_millis(1500)),
+                    ..event(Some("1"), "update", "")
+                },
+                event(Some("1"), "message", "{}"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use bytes::Bytes;
+use http::HeaderMap;
+use http_body::{Frame, SizeHint};
+use http_body_util::BodyExt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::Ordering;
+use std::task::{Context, Poll, ready};
+use tokio::time::Instant;
+use tracing::field::Empty;
+use tracing::{Instrument, Span};
+
+use crate::body::ResponseBody;
+use crate::connect::PeerInfo;
+use crate::{Error, Request, Response, Result};
+
+/// Creates the span of the execution of `request`. The status of the
+/// response and the duration are recorded by [`instrument`].
+pub(crate) fn request_span(request: &Request) -> Span {
+    tracing::debug_span!(
+        "request",
+        method = %request.method(),
+        socket = %request.url().socket_path().display(),
+        path = request.url().path(),
+        status = Empty,
+        duration = Empty,
+    )
+}
+
+/// Runs the execution of a request in its `span`.
+pub(crate) async fn instrument<F>(execute: F, span: Span) -> Result<Response>
+where
+    F: Future<Output = Result<Response>>,
+{
+    let start = Instant::now();
+    let result = execute.instrument(span.clone()).await;
+
+    span.record("duration", tracing::field::debug(start.elapsed()));
+    match &result {
+        Ok(response) => {
+            span.record("status", response.status().as_u16());
+        }
+        Err(err) => tracing::debug!(parent: &span, error = %err, "request failed"),
+    }
+    result
+}
+
+/// Logs the headers of a request about to be sent.
+///
+/// Values marked as sensitive, such as those set by `basic_auth()` and
+/// `bearer_auth()`, are formatted as `Sensitive` by their `Debug` impl.
+pub(crate) fn sending(headers: &HeaderMap) {
+    tracing::trace!(?headers, "sending request");
+}
+
+/// Logs whether the response was received over a reused pooled connection.
+pub(crate) fn received<B>(response: &http::Response<B>) {
+    let Some(peer) = response.extensions().get::<PeerInfo>() else {
+        return;
+    };
+    let requests = peer.requests.fetch_add(1, Ordering::Relaxed) + 1;
+    if requests > 1 {
+        tracing::debug!(
+            socket = %peer.path.display(),
+            requests,
+            "reused pooled connection"
+        );
+    }
+}
+
+/// Wraps a response body to log when it has been read to the end, in the
+/// span of the request.
+pub(crate) fn body(body: ResponseBody) -> ResponseBody {
+    TracedBody {
+        inner: body,
+        span: Span::current(),
+        start: Instant::now(),
+        bytes: 0,
+        done: false,
+    }
+    .boxed()
+}
+
+struct TracedBody {
+    inner: ResponseBody,
+    span: Span,
+    start: Instant,
+    bytes: u64,
+    done: bool,
+}
+
+impl http_body::Body for TracedBody {
+    type Data = Bytes;
+    type Error = Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<std::result::Result<Frame<Self::Data>, Self::Error>>> {
+        let frame = ready!(Pin::new(&mut self.inner).poll_frame(cx));
+        match &frame {
+            Some(Ok(frame)) => {
+                if let Some(data) = frame.data_ref() {
+                    self.bytes += data.len() as u64;
+                }
+            }
+            Some(Err(err)) if !self.done => {
+                self.done = true;
+                tracing::debug!(parent: &self.span, error = %err, "response body failed");
+            }
+            None if !self.done => {
+                self.done = true;
+                tracing::debug!(
+                    parent: &self.span,
+                    bytes = self.bytes,
+                    duration = ?self.start.elapsed(),
+                    "response body read"
+                );
+            }
+            _ => {}
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
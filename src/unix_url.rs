@@ -1,10 +1,15 @@
 use http::Uri;
+use std::ffi::{OsStr, OsString};
 use std::fmt;
-use std::path::Path;
-use std::str::Split;
-use url::{Origin, PathSegmentsMut, UrlQuery};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::path::{Path, PathBuf};
+use std::str::{FromStr, Split};
+use url::{Origin, PathSegmentsMut, Position, UrlQuery};
 use url::{ParseError, Url, form_urlencoded::Serializer};
 
+use crate::Error;
+use crate::error::BuilderError;
+
 /// A wrapper around `Url` representing a URL over a UNIX domain socket.
 ///
 /// `UnixUrl` encapsulates a UNIX socket path and a relative URL path into a
@@ -23,6 +28,11 @@ use url::{ParseError, Url, form_urlencoded::Serializer};
 /// assert_eq!(
 ///     unix_url.to_string(),
 ///     "unix://2f746d702f6d792e736f636b6574/api/v1/status");
+///
+/// // The alternate form is readable, and can be parsed back.
+/// assert_eq!(
+///     format!("{unix_url:#}"),
+///     "unix:/tmp/my.socket:/api/v1/status");
 /// ```
 #[derive(Debug, Clone, PartialEq)]
 pub struct UnixUrl {
@@ -47,8 +57,51 @@ impl UnixUrl {
     where
         P: AsRef<Path>,
     {
-        let encoded_socket = hex::encode(socket.as_ref().to_string_lossy().as_bytes());
+        let encoded_socket = Self::encode_socket(socket);
+        Self::from_encoded(&encoded_socket, path)
+    }
 
+    /// Creates a new `UnixUrl` from the name of a Linux abstract socket and a
+    /// relative URL path.
+    ///
+    /// Abstract sockets are not bound to the filesystem. The name is given
+    /// without its leading NUL byte, which is added when encoding the host,
+    /// so that `@name` in `ss` or `netstat` output is written `"name"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseError` if the final URL is not syntactically valid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http_unix_client::UnixUrl;
+    ///
+    /// let unix_url = UnixUrl::abstract_socket("my.socket", "/status").unwrap();
+    ///
+    /// assert_eq!(unix_url.to_string(), "unix://006d792e736f636b6574/status");
+    /// ```
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "linux", target_os = "android"))))]
+    pub fn abstract_socket<N>(name: N, path: &str) -> Result<Self, ParseError>
+    where
+        N: AsRef<[u8]>,
+    {
+        let mut socket = vec![0];
+        socket.extend_from_slice(name.as_ref());
+        Self::from_encoded(&hex::encode(socket), path)
+    }
+
+    /// Hex-encodes a socket path into the host of a `unix://` URL.
+    pub(crate) fn encode_socket<P>(socket: P) -> String
+    where
+        P: AsRef<Path>,
+    {
+        hex::encode(socket.as_ref().as_os_str().as_bytes())
+    }
+
+    /// Creates a new `UnixUrl` from an already hex-encoded socket path.
+    pub(crate) fn from_encoded(encoded_socket: &str, path: &str) -> Result<Self, ParseError> {
         let normalized_path = if path.starts_with('/') {
             path.to_string()
         } else {
@@ -61,6 +114,33 @@ impl UnixUrl {
         Ok(Self { inner: url })
     }
 
+    /// Returns the path of the socket, decoded from the host of the URL.
+    ///
+    /// The path of an abstract socket starts with a NUL byte.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use http_unix_client::UnixUrl;
+    /// use std::path::Path;
+    ///
+    /// let unix_url = UnixUrl::new("/tmp/my.socket", "/status").unwrap();
+    ///
+    /// assert_eq!(unix_url.socket_path(), Path::new("/tmp/my.socket"));
+    /// ```
+    pub fn socket_path(&self) -> PathBuf {
+        self.inner
+            .host_str()
+            .and_then(Self::decode_socket)
+            .unwrap_or_default()
+    }
+
+    /// Decodes a socket path from the host of a `unix://` URL.
+    pub(crate) fn decode_socket(host: &str) -> Option<PathBuf> {
+        let bytes = hex::decode(host).ok()?;
+        Some(PathBuf::from(OsString::from_vec(bytes)))
+    }
+
     /// Returns the full URL as a string.
     pub fn as_str(&self) -> &str {
         self.inner.as_str()
@@ -122,9 +202,58 @@ impl UnixUrl {
     }
 }
 
+/// Formats the URL with its hex-encoded host, or with the alternate flag
+/// (`{:#}`) in the readable form `unix:/tmp/my.socket:/api/v1/status`.
+///
+/// Abstract sockets are written `unix:@name:/path` in the readable form.
+/// Socket paths which are not valid UTF-8 are written lossily. The readable
+/// form can only be parsed back if the socket path is absolute or abstract,
+/// and contains no colon.
 impl fmt::Display for UnixUrl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.inner)
+        if !f.alternate() {
+            return write!(f, "{}", self.inner);
+        }
+
+        let socket = self.socket_path().into_os_string().into_vec();
+        match socket.strip_prefix(b"\0") {
+            Some(name) => write!(f, "unix:@{}:", String::from_utf8_lossy(name))?,
+            None => write!(f, "unix:{}:", String::from_utf8_lossy(&socket))?,
+        }
+        f.write_str(&self.inner[Position::BeforePath..])
+    }
+}
+
+/// Serializes the URL in its readable form, unless the socket path is
+/// relative, is not valid UTF-8 or contains a colon, in which case the
+/// hex-encoded form is used.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for UnixUrl {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let socket = self.socket_path();
+        match socket.to_str() {
+            Some(socket) if socket.starts_with(['/', '\0']) && !socket.contains(':') => {
+                serializer.collect_str(&format_args!("{self:#}"))
+            }
+            _ => serializer.serialize_str(self.as_str()),
+        }
+    }
+}
+
+/// Deserializes the URL from any of the formats parsed by `UnixUrl::from_str()`.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for UnixUrl {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let url = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        url.parse().map_err(serde::de::Error::custom)
     }
 }
 
@@ -144,6 +273,127 @@ impl TryFrom<UnixUrl> for Uri {
     }
 }
 
+/// Parses a `UnixUrl` from one of the common string formats:
+///
+/// - `unix://2f746d702f6d792e736f636b6574/api`, as displayed by `UnixUrl`.
+/// - `unix:///var/run/docker.sock`, where the whole path is the socket.
+/// - `http+unix://%2Fvar%2Frun%2Fdocker.sock/v1/info`, with a percent-encoded socket.
+/// - `unix:/var/run/docker.sock:/v1/info`, with the path after a colon.
+/// - `unix:@name:/v1/info`, for a Linux abstract socket.
+///
+/// # Example
+///
+/// ```
+/// use http_unix_client::UnixUrl;
+///
+/// let unix_url: UnixUrl = "http+unix://%2Ftmp%2Fmy.socket/status".parse().unwrap();
+///
+/// assert_eq!(unix_url, UnixUrl::new("/tmp/my.socket", "/status").unwrap());
+/// ```
+impl FromStr for UnixUrl {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::from(BuilderError::InvalidUnixUrl(s.to_owned()));
+
+        if let Some(rest) = s.strip_prefix("http+unix://") {
+            let (host, path) = split_host(rest);
+            let socket = percent_decode(host).ok_or_else(invalid)?;
+            Ok(Self::new(OsStr::from_bytes(&socket), path)?)
+        } else if let Some(socket) = s.strip_prefix("unix://").filter(|s| s.starts_with('/')) {
+            Ok(Self::new(socket, "/")?)
+        } else if let Some(rest) = s.strip_prefix("unix://") {
+            let (host, path) = split_host(rest);
+            Self::decode_socket(host)
+                .filter(|socket| !socket.as_os_str().is_empty())
+                .ok_or_else(invalid)?;
+            Ok(Self::from_encoded(host, path)?)
+        } else if let Some(rest) = s.strip_prefix("unix:").filter(|s| s.starts_with('/')) {
+            let (socket, path) = rest.split_once(':').unwrap_or((rest, "/"));
+            Ok(Self::new(socket, path)?)
+        } else if let Some(rest) = s.strip_prefix("unix:@") {
+            let (name, path) = rest.split_once(':').unwrap_or((rest, "/"));
+            Ok(Self::new(format!("\0{name}"), path)?)
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+impl TryFrom<&str> for UnixUrl {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Splits the host of a URL, without its scheme, from its path.
+fn split_host(url: &str) -> (&str, &str) {
+    match url.find(['/', '?', '#']) {
+        Some(index) => url.split_at(index),
+        None => (url, "/"),
+    }
+}
+
+/// Decodes a percent-encoded string, failing on an empty or invalid one.
+fn percent_decode(input: &str) -> Option<Vec<u8>> {
+    let mut bytes = input.bytes();
+    let mut decoded = Vec::with_capacity(input.len());
+    while let Some(byte) = bytes.next() {
+        if byte == b'%' {
+            let hex = [bytes.next()?, bytes.next()?];
+            decoded.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            decoded.push(byte);
+        }
+    }
+    (!decoded.is_empty()).then_some(decoded)
+}
+
+/// A trait to try to convert some type into a [`UnixUrl`].
+///
+/// This trait is "sealed", such that only types within this crate can
+/// implement it.
+pub trait IntoUnixUrl: sealed::IntoUnixUrlSealed {}
+
+impl IntoUnixUrl for UnixUrl {}
+impl IntoUnixUrl for &str {}
+impl IntoUnixUrl for &String {}
+impl IntoUnixUrl for String {}
+
+mod sealed {
+    use super::UnixUrl;
+
+    pub trait IntoUnixUrlSealed {
+        fn into_unix_url(self) -> crate::Result<UnixUrl>;
+    }
+
+    impl IntoUnixUrlSealed for UnixUrl {
+        fn into_unix_url(self) -> crate::Result<UnixUrl> {
+            Ok(self)
+        }
+    }
+
+    impl IntoUnixUrlSealed for &str {
+        fn into_unix_url(self) -> crate::Result<UnixUrl> {
+            self.parse()
+        }
+    }
+
+    impl IntoUnixUrlSealed for &String {
+        fn into_unix_url(self) -> crate::Result<UnixUrl> {
+            self.parse()
+        }
+    }
+
+    impl IntoUnixUrlSealed for String {
+        fn into_unix_url(self) -> crate::Result<UnixUrl> {
+            self.parse()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,6 +417,95 @@ mod tests {
         assert_eq!(url.fragment(), Some("frag"));
     }
 
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn test_abstract_socket() {
+        let url = UnixUrl::abstract_socket("my.socket", "/status").unwrap();
+        assert_eq!(url, UnixUrl::new("\0my.socket", "/status").unwrap());
+        assert_eq!(url.as_url().host_str(), Some("006d792e736f636b6574"));
+    }
+
+    #[test]
+    fn test_socket_path_round_trip() {
+        let socket = Path::new(std::ffi::OsStr::from_bytes(b"/tmp/caf\xe9.socket"));
+        let url = UnixUrl::new(socket, "/").unwrap();
+        assert_eq!(
+            url.as_url().host_str(),
+            Some("2f746d702f636166e92e736f636b6574")
+        );
+        assert_eq!(url.socket_path(), socket);
+    }
+
+    #[test]
+    fn test_from_str() {
+        let expected = UnixUrl::new("/var/run/docker.sock", "/v1/info?all=1").unwrap();
+        for input in [
+            "unix://2f7661722f72756e2f646f636b65722e736f636b/v1/info?all=1",
+            "http+unix://%2Fvar%2Frun%2Fdocker.sock/v1/info?all=1",
+            "unix:/var/run/docker.sock:/v1/info?all=1",
+        ] {
+            assert_eq!(input.parse::<UnixUrl>().unwrap(), expected, "{input}");
+        }
+
+        let root = UnixUrl::new("/var/run/docker.sock", "/").unwrap();
+        assert_eq!(
+            "unix:///var/run/docker.sock".parse::<UnixUrl>().unwrap(),
+            root
+        );
+        assert_eq!(
+            "unix:/var/run/docker.sock".parse::<UnixUrl>().unwrap(),
+            root
+        );
+        assert_eq!(root.to_string().parse::<UnixUrl>().unwrap(), root);
+    }
+
+    #[test]
+    fn test_readable_display() {
+        let url = UnixUrl::new("/tmp/my.socket", "/api?x=1#top").unwrap();
+        assert_eq!(format!("{url:#}"), "unix:/tmp/my.socket:/api?x=1#top");
+        assert_eq!(format!("{url:#}").parse::<UnixUrl>().unwrap(), url);
+
+        let url = UnixUrl::new("\0my.socket", "/").unwrap();
+        assert_eq!(format!("{url:#}"), "unix:@my.socket:/");
+        assert_eq!(format!("{url:#}").parse::<UnixUrl>().unwrap(), url);
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "json"))]
+    fn test_serde() {
+        let url = UnixUrl::new("/tmp/my.socket", "/api").unwrap();
+        let json = serde_json::to_string(&url).unwrap();
+        assert_eq!(json, r#""unix:/tmp/my.socket:/api""#);
+        assert_eq!(serde_json::from_str::<UnixUrl>(&json).unwrap(), url);
+
+        let url = UnixUrl::new("\0my.socket", "/api").unwrap();
+        let json = serde_json::to_string(&url).unwrap();
+        assert_eq!(json, r#""unix:@my.socket:/api""#);
+        assert_eq!(serde_json::from_str::<UnixUrl>(&json).unwrap(), url);
+
+        for socket in ["/tmp/my:socket", "\0my:socket", "run/app.sock"] {
+            let url = UnixUrl::new(socket, "/api").unwrap();
+            let json = serde_json::to_string(&url).unwrap();
+            assert_eq!(json, format!("\"{url}\""), "{socket:?}");
+            assert_eq!(serde_json::from_str::<UnixUrl>(&json).unwrap(), url);
+        }
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        for input in [
+            "http://localhost/",
+            "unix://",
+            "unix://?x=1",
+            "unix://not-hex/",
+            "http+unix:///v1/info",
+            "http+unix://%2/",
+            "unix:relative.sock",
+        ] {
+            assert!(input.parse::<UnixUrl>().is_err(), "{input}");
+        }
+    }
+
     #[test]
     fn test_query_mutation() {
         let mut url = UnixUrl::new("/tmp/my.socket", "/foo").unwrap();
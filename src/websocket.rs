@@ -0,0 +1,199 @@
+//! WebSocket clients over Unix sockets.
+//!
+//! A WebSocket is opened from a [`RequestBuilder`]
+//! with `RequestBuilder::websocket()`, so it uses the same [`UnixUrl`](crate::UnixUrl)
+//! addressing, default headers and authentication as any other request.
+//!
+//! A [`WebSocket`] is a [`Stream`] of received [`Message`]s, and a [`Sink`]
+//! of messages to send.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use http_unix_client::{Client, Error, websocket::Message};
+//! # use futures_util::{SinkExt, StreamExt};
+//! #
+//! # async fn run() -> Result<(), Error> {
+//! let mut websocket = Client::new()
+//!     .get("/run/my-daemon.sock", "/events")
+//!     .bearer_auth("token")
+//!     .websocket()
+//!     .await?;
+//!
+//! websocket.send(Message::Text("subscribe".into())).await?;
+//! while let Some(message) = websocket.next().await {
+//!     if let Message::Text(text) = message? {
+//!         println!("{text}");
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Optional
+//!
+//! This requires the optional `websocket` feature to be enabled.
+
+use bytes::Bytes;
+use futures_util::{Sink, Stream};
+use http::header::{SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, SEC_WEBSOCKET_VERSION};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use thiserror::Error;
+use tokio_tungstenite::WebSocketStream;
+use tokio_tungstenite::tungstenite::{self, handshake, protocol};
+
+use crate::{Error, RequestBuilder, Result, StatusCode, Upgraded};
+
+/// A message sent or received over a [`WebSocket`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// A text message.
+    Text(String),
+    /// A binary message.
+    Binary(Bytes),
+    /// A ping, answered automatically with a pong by the receiving end.
+    Ping(Bytes),
+    /// A pong, the answer to a ping.
+    Pong(Bytes),
+    /// A close message, with an optional close frame.
+    Close(Option<CloseFrame>),
+}
+
+/// The payload of a close [`Message`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloseFrame {
+    /// The close code, such as `1000` for a normal closure.
+    pub code: u16,
+    /// The reason of the closure.
+    pub reason: String,
+}
+
+impl From<Message> for tungstenite::Message {
+    fn from(message: Message) -> Self {
+        match message {
+            Message::Text(text) => Self::Text(text.into()),
+            Message::Binary(data) => Self::Binary(data),
+            Message::Ping(data) => Self::Ping(data),
+            Message::Pong(data) => Self::Pong(data),
+            Message::Close(frame) => Self::Close(frame.map(|frame| protocol::CloseFrame {
+                code: frame.code.into(),
+                reason: frame.reason.into(),
+            })),
+        }
+    }
+}
+
+impl Message {
+    /// Converts a received message. Raw frames are never received.
+    fn from_tungstenite(message: tungstenite::Message) -> Option<Self> {
+        match message {
+            tungstenite::Message::Text(text) => Some(Self::Text(text.as_str().to_owned())),
+            tungstenite::Message::Binary(data) => Some(Self::Binary(data)),
+            tungstenite::Message::Ping(data) => Some(Self::Ping(data)),
+            tungstenite::Message::Pong(data) => Some(Self::Pong(data)),
+            tungstenite::Message::Close(frame) => {
+                Some(Self::Close(frame.map(|frame| CloseFrame {
+                    code: frame.code.into(),
+                    reason: frame.reason.as_str().to_owned(),
+                })))
+            }
+            tungstenite::Message::Frame(_) => None,
+        }
+    }
+}
+
+/// A WebSocket connection over a Unix socket.
+///
+/// To open a `WebSocket`, refer to `RequestBuilder::websocket()`.
+#[derive(Debug)]
+pub struct WebSocket {
+    inner: WebSocketStream<Upgraded>,
+}
+
+impl Stream for WebSocket {
+    type Item = Result<Message>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            return match Pin::new(&mut self.inner).poll_next(cx) {
+                Poll::Ready(Some(Ok(message))) => match Message::from_tungstenite(message) {
+                    Some(message) => Poll::Ready(Some(Ok(message))),
+                    None => continue,
+                },
+                Poll::Ready(Some(Err(err))) => Poll::Ready(Some(Err(websocket_error(err)))),
+                Poll::Ready(None) => Poll::Ready(None),
+                Poll::Pending => Poll::Pending,
+            };
+        }
+    }
+}
+
+impl Sink<Message> for WebSocket {
+    type Error = Error;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(&mut self.inner)
+            .poll_ready(cx)
+            .map_err(websocket_error)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, message: Message) -> Result<()> {
+        Pin::new(&mut self.inner)
+            .start_send(message.into())
+            .map_err(websocket_error)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(&mut self.inner)
+            .poll_flush(cx)
+            .map_err(websocket_error)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Pin::new(&mut self.inner)
+            .poll_close(cx)
+            .map_err(websocket_error)
+    }
+}
+
+/// Performs the opening handshake of `request`, and returns the `WebSocket`.
+pub(crate) async fn connect(request: RequestBuilder) -> Result<WebSocket> {
+    let key = handshake::client::generate_key();
+    let response = request
+        .upgrade("websocket")
+        .header(SEC_WEBSOCKET_KEY, &key)
+        .header(SEC_WEBSOCKET_VERSION, "13")
+        .send()
+        .await?;
+
+    if response.status() != StatusCode::SWITCHING_PROTOCOLS {
+        return Err(HandshakeError::Status(response.status()).into());
+    }
+    let accept = response.headers().get(SEC_WEBSOCKET_ACCEPT);
+    if accept.is_none_or(|accept| accept != &handshake::derive_accept_key(key.as_bytes())) {
+        return Err(HandshakeError::Accept.into());
+    }
+
+    let upgraded = response.upgrade().await?;
+    let inner = WebSocketStream::from_raw_socket(upgraded, protocol::Role::Client, None).await;
+    Ok(WebSocket { inner })
+}
+
+fn websocket_error(err: tungstenite::Error) -> Error {
+    Error::WebSocket(err.into())
+}
+
+#[derive(Debug, Error)]
+enum HandshakeError {
+    #[error("server answered the WebSocket handshake with {0}")]
+    Status(StatusCode),
+    #[error("invalid `Sec-WebSocket-Accept` header in the WebSocket handshake")]
+    Accept,
+}
+
+impl From<HandshakeError> for Error {
+    fn from(err: HandshakeError) -> Self {
+        Self::WebSocket(err.into())
+    }
+}
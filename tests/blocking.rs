@@ -0,0 +1,56 @@
+#![cfg(feature = "blocking")]
+
+use actix_web::HttpResponse;
+use actix_web::http::Method;
+use actix_web::rt::task::spawn_blocking;
+use http_unix_client::blocking::Client;
+use std::io::{self, Read};
+
+mod server;
+
+#[actix_web::test]
+async fn blocking_response_text() -> io::Result<()> {
+    let server = server::setup_test_server("blocking_text", "/test", Method::GET, async || {
+        HttpResponse::Ok().body("Hello, World!")
+    })
+    .await?;
+
+    let text = spawn_blocking(|| {
+        Client::new()
+            .get("/tmp/blocking_text.socket", "/test")
+            .send()?
+            .error_for_status()?
+            .text()
+    })
+    .await?
+    .map_err(io::Error::other)?;
+    assert_eq!(text, "Hello, World!");
+
+    drop(server);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn blocking_response_read() -> io::Result<()> {
+    let server = server::setup_test_server("blocking_read", "/test", Method::GET, async || {
+        HttpResponse::Ok().body("Hello, World!")
+    })
+    .await?;
+
+    let body = spawn_blocking(|| -> io::Result<String> {
+        let mut resp = Client::new()
+            .get("/tmp/blocking_read.socket", "/test")
+            .send()
+            .map_err(io::Error::other)?;
+        let mut body = String::new();
+        resp.read_to_string(&mut body)?;
+        Ok(body)
+    })
+    .await??;
+    assert_eq!(body, "Hello, World!");
+
+    drop(server);
+
+    Ok(())
+}
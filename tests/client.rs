@@ -147,3 +147,1311 @@ async fn response_fail() -> io::Result<()> {
 
     Ok(())
 }
+
+#[actix_web::test]
+async fn client_default_headers() -> io::Result<()> {
+    use actix_web::HttpRequest;
+    use http::{HeaderMap, HeaderValue};
+
+    let server = server::setup_test_server(
+        "default_headers",
+        "/test",
+        Method::GET,
+        async |req: HttpRequest| {
+            let header = |name| {
+                req.headers()
+                    .get(name)
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or_default()
+                    .to_owned()
+            };
+            HttpResponse::Ok().body(format!("{} {}", header("user-agent"), header("x-api")))
+        },
+    )
+    .await?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-api", HeaderValue::from_static("default"));
+    let client = Client::builder()
+        .user_agent("test-agent/1.0")
+        .default_headers(headers)
+        .build()
+        .map_err(io::Error::other)?;
+
+    let resp = client
+        .get("/tmp/default_headers.socket", "/test")
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(&resp.text().await.unwrap(), "test-agent/1.0 default");
+
+    let resp = client
+        .get("/tmp/default_headers.socket", "/test")
+        .header("x-api", "override")
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(&resp.text().await.unwrap(), "test-agent/1.0 override");
+
+    drop(server);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn http1_max_buf_size_too_small() {
+    let err = Client::builder()
+        .http1_max_buf_size(1024)
+        .build()
+        .unwrap_err();
+    assert!(err.is_builder());
+
+    assert!(Client::builder().http1_max_buf_size(8192).build().is_ok());
+}
+
+#[actix_web::test]
+async fn request_timeout() -> io::Result<()> {
+    use std::time::Duration;
+
+    let server = server::setup_test_server("timeout", "/test", Method::GET, async || {
+        actix_web::rt::time::sleep(Duration::from_secs(2)).await;
+        HttpResponse::Ok().finish()
+    })
+    .await?;
+
+    let err = Client::new()
+        .get("/tmp/timeout.socket", "/test")
+        .timeout(Duration::from_millis(100))
+        .send()
+        .await
+        .expect_err("request should time out");
+    assert!(err.is_timeout());
+
+    let client = Client::builder()
+        .timeout(Duration::from_millis(100))
+        .build()
+        .map_err(io::Error::other)?;
+    let err = client
+        .get("/tmp/timeout.socket", "/test")
+        .send()
+        .await
+        .expect_err("request should time out");
+    assert!(err.is_timeout());
+    assert!(!err.is_connect());
+
+    drop(server);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn connect_error_names_socket() {
+    let socket_path = "/tmp/missing.socket";
+    let _ = std::fs::remove_file(socket_path);
+
+    let err = Client::new()
+        .get(socket_path, "/")
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.is_connect());
+    assert!(err.to_string().contains(socket_path), "{err}");
+}
+
+#[actix_web::test]
+async fn request_body_stream() -> io::Result<()> {
+    use actix_web::{HttpRequest, web::Bytes};
+    use http_unix_client::Body;
+
+    let server = server::setup_test_server(
+        "body_stream",
+        "/test",
+        Method::POST,
+        async |req: HttpRequest, body: Bytes| {
+            let encoding = req
+                .headers()
+                .get("transfer-encoding")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default()
+                .to_owned();
+            HttpResponse::Ok().body(format!("{encoding} {}", String::from_utf8_lossy(&body)))
+        },
+    )
+    .await?;
+
+    let chunks: Vec<io::Result<&'static str>> = vec![Ok("Hello"), Ok(", "), Ok("World!")];
+    let resp = Client::new()
+        .post("/tmp/body_stream.socket", "/test")
+        .body(Body::wrap_stream(futures_util::stream::iter(chunks)))
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(&resp.text().await.unwrap(), "chunked Hello, World!");
+
+    let resp = Client::new()
+        .post("/tmp/body_stream.socket", "/test")
+        .body(Body::from_async_read(&b"Hello, Reader!"[..]))
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(&resp.text().await.unwrap(), "chunked Hello, Reader!");
+
+    // A `Cell` makes the stream `Send` but not `Sync`.
+    let seen = std::cell::Cell::new(0);
+    let chunks: Vec<io::Result<&'static str>> = vec![Ok("Hello, "), Ok("Send!")];
+    let stream = futures_util::StreamExt::inspect(futures_util::stream::iter(chunks), move |_| {
+        seen.set(seen.get() + 1);
+    });
+    let resp = Client::new()
+        .post("/tmp/body_stream.socket", "/test")
+        .body(Body::wrap_stream(stream))
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(&resp.text().await.unwrap(), "chunked Hello, Send!");
+
+    drop(server);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn response_bytes_stream() -> io::Result<()> {
+    use futures_util::TryStreamExt;
+    use tokio::io::AsyncReadExt;
+
+    let server = server::setup_test_server("bytes_stream", "/test", Method::GET, async || {
+        HttpResponse::Ok().body("Hello, World!")
+    })
+    .await?;
+
+    let chunks: Vec<_> = Client::new()
+        .get("/tmp/bytes_stream.socket", "/test")
+        .send()
+        .await
+        .map_err(io::Error::other)?
+        .bytes_stream()
+        .try_collect()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(chunks.concat(), b"Hello, World!");
+
+    let mut body = String::new();
+    Client::new()
+        .get("/tmp/bytes_stream.socket", "/test")
+        .send()
+        .await
+        .map_err(io::Error::other)?
+        .into_async_read()
+        .read_to_string(&mut body)
+        .await?;
+    assert_eq!(body, "Hello, World!");
+
+    drop(server);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn response_trailers() -> io::Result<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::UnixListener;
+
+    let socket_path = "/tmp/trailers.socket";
+    let _ = std::fs::remove_file(socket_path);
+    let listener = UnixListener::bind(socket_path)?;
+    let server = actix_web::rt::spawn(async move {
+        let (mut stream, _) = listener.accept().await?;
+        let mut buf = [0; 1024];
+        let _ = stream.read(&mut buf).await?;
+        stream
+            .write_all(
+                b"HTTP/1.1 200 OK\r\n\
+                  transfer-encoding: chunked\r\n\
+                  trailer: x-checksum\r\n\r\n\
+                  5\r\nHello\r\n\
+                  0\r\nx-checksum: abc\r\n\r\n",
+            )
+            .await
+    });
+
+    let mut resp = Client::new()
+        .get(socket_path, "/")
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert!(resp.trailers().is_none());
+    let mut body = Vec::new();
+    while let Some(chunk) = resp.chunk().await.map_err(io::Error::other)? {
+        body.extend_from_slice(&chunk);
+    }
+    assert_eq!(body, b"Hello");
+    let trailers = resp.trailers().expect("trailers");
+    assert_eq!(trailers["x-checksum"], "abc");
+
+    server.await??;
+
+    Ok(())
+}
+
+#[actix_web::test]
+#[cfg(feature = "gzip")]
+async fn response_gzip() -> io::Result<()> {
+    use actix_web::HttpRequest;
+    use async_compression::tokio::bufread::GzipEncoder;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let server =
+        server::setup_test_server("gzip", "/test", Method::GET, async |req: HttpRequest| {
+            if req.query_string() == "empty" {
+                return HttpResponse::NoContent()
+                    .insert_header(("content-encoding", "gzip"))
+                    .finish();
+            }
+            let body = "Hello, World! ".repeat(64);
+            let mut gzipped = Vec::new();
+            GzipEncoder::new(body.as_bytes())
+                .read_to_end(&mut gzipped)
+                .await
+                .unwrap();
+            HttpResponse::Ok()
+                .insert_header(("content-encoding", "gzip"))
+                .body(gzipped)
+        })
+        .await?;
+
+    let resp = Client::new()
+        .get("/tmp/gzip.socket", "/test")
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert!(resp.headers().get("content-encoding").is_none());
+    assert_eq!(resp.content_length(), None);
+    assert_eq!(resp.text().await.unwrap(), "Hello, World! ".repeat(64));
+
+    let resp = Client::new()
+        .get("/tmp/gzip.socket", "/test")
+        .no_decompress()
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(resp.headers()["content-encoding"], "gzip");
+    assert_ne!(resp.bytes().await.unwrap(), "Hello, World! ".repeat(64));
+
+    let resp = Client::new()
+        .get("/tmp/gzip.socket", "/test?empty")
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(resp.status(), http_unix_client::StatusCode::NO_CONTENT);
+    assert!(resp.bytes().await.unwrap().is_empty());
+
+    drop(server);
+
+    // A `HEAD` response describes the body it would have, without one.
+    let socket_path = "/tmp/gzip_head.socket";
+    let _ = std::fs::remove_file(socket_path);
+    let listener = tokio::net::UnixListener::bind(socket_path)?;
+    let server = actix_web::rt::spawn(async move {
+        let (mut stream, _) = listener.accept().await?;
+        let mut buf = [0; 1024];
+        let _ = stream.read(&mut buf).await?;
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-encoding: gzip\r\ncontent-length: 100\r\n\r\n")
+            .await
+    });
+
+    let resp = Client::new()
+        .head(socket_path, "/test")
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(resp.headers()["content-encoding"], "gzip");
+    assert_eq!(resp.headers()["content-length"], "100");
+    assert!(resp.bytes().await.unwrap().is_empty());
+
+    server.await??;
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn retry_on_status() -> io::Result<()> {
+    use http_unix_client::{StatusCode, retry};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    static HITS: AtomicUsize = AtomicUsize::new(0);
+
+    let server = server::setup_test_server("retry", "/test", Method::GET, async || {
+        if HITS.fetch_add(1, Ordering::SeqCst) < 2 {
+            HttpResponse::ServiceUnavailable()
+                .insert_header(("retry-after", "0"))
+                .finish()
+        } else {
+            HttpResponse::Ok().body("Hello, World!")
+        }
+    })
+    .await?;
+
+    let policy = retry::Policy::attempts(3)
+        .backoff(Duration::from_millis(1), Duration::from_millis(10))
+        .retry_on_status(StatusCode::SERVICE_UNAVAILABLE);
+
+    let resp = Client::builder()
+        .retry(policy)
+        .build()
+        .map_err(io::Error::other)?
+        .get("/tmp/retry.socket", "/test")
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(HITS.load(Ordering::SeqCst), 3);
+
+    HITS.store(0, Ordering::SeqCst);
+    let resp = Client::new()
+        .get("/tmp/retry.socket", "/test")
+        .retry(
+            retry::Policy::attempts(2)
+                .jitter(false)
+                .retry_on_status(StatusCode::SERVICE_UNAVAILABLE),
+        )
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(HITS.load(Ordering::SeqCst), 2);
+
+    drop(server);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn retry_within_timeout() -> io::Result<()> {
+    use http_unix_client::{StatusCode, retry};
+    use std::time::{Duration, Instant};
+
+    let server = server::setup_test_server("retry_timeout", "/test", Method::GET, async || {
+        HttpResponse::ServiceUnavailable().finish()
+    })
+    .await?;
+
+    let policy = retry::Policy::attempts(3)
+        .backoff(Duration::from_secs(5), Duration::from_secs(5))
+        .jitter(false)
+        .retry_on_status(StatusCode::SERVICE_UNAVAILABLE);
+
+    let start = Instant::now();
+    let err = Client::builder()
+        .retry(policy)
+        .timeout(Duration::from_millis(200))
+        .build()
+        .map_err(io::Error::other)?
+        .get("/tmp/retry_timeout.socket", "/test")
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.is_timeout());
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    drop(server);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn redirect() -> io::Result<()> {
+    use actix_web::{App, HttpServer, web};
+    use http_unix_client::{StatusCode, redirect};
+
+    let socket_path = "/tmp/redirect.socket";
+    let _ = std::fs::remove_file(socket_path);
+    let server = HttpServer::new(|| {
+        App::new()
+            .route(
+                "/v1/items",
+                web::post().to(async || {
+                    HttpResponse::SeeOther()
+                        .insert_header(("location", "/v2/items/"))
+                        .finish()
+                }),
+            )
+            .route(
+                "/v2/items/",
+                web::get().to(async || HttpResponse::Ok().body("items")),
+            )
+            .route(
+                "/loop",
+                web::get().to(async || {
+                    HttpResponse::TemporaryRedirect()
+                        .insert_header(("location", "loop"))
+                        .finish()
+                }),
+            )
+            .route(
+                "/tcp",
+                web::get().to(async || {
+                    HttpResponse::MovedPermanently()
+                        .insert_header(("location", "http://localhost/v2/items/"))
+                        .finish()
+                }),
+            )
+    })
+    .bind_uds(socket_path)?
+    .run();
+    let handle = server.handle();
+    actix_web::rt::spawn(server);
+    actix_web::rt::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    let resp = Client::new()
+        .post(socket_path, "/v1/items")
+        .body("item")
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.url().path(), "/v2/items/");
+    assert_eq!(resp.text().await.unwrap(), "items");
+
+    let err = Client::new()
+        .get(socket_path, "/loop")
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.is_redirect());
+
+    let err = Client::new()
+        .get(socket_path, "/tcp")
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.is_redirect());
+
+    let resp = Client::builder()
+        .redirect(redirect::Policy::default().allow_http(true))
+        .build()
+        .map_err(io::Error::other)?
+        .get(socket_path, "/tcp")
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(resp.url().path(), "/v2/items/");
+
+    let resp = Client::builder()
+        .redirect(redirect::Policy::none())
+        .build()
+        .map_err(io::Error::other)?
+        .get(socket_path, "/tcp")
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(resp.status(), StatusCode::MOVED_PERMANENTLY);
+
+    handle.stop(false).await;
+    let _ = std::fs::remove_file(socket_path);
+
+    Ok(())
+}
+
+#[actix_web::test]
+#[cfg(feature = "cookies")]
+async fn cookie_store() -> io::Result<()> {
+    use actix_web::HttpRequest;
+
+    let server =
+        server::setup_test_server("cookies", "/test", Method::GET, async |req: HttpRequest| {
+            match req.cookie("session") {
+                Some(cookie) => HttpResponse::Ok().body(cookie.value().to_string()),
+                None => HttpResponse::Ok()
+                    .cookie(
+                        actix_web::cookie::Cookie::build("session", "42")
+                            .path("/")
+                            .finish(),
+                    )
+                    .finish(),
+            }
+        })
+        .await?;
+
+    let client = Client::builder()
+        .cookie_store(true)
+        .build()
+        .map_err(io::Error::other)?;
+
+    let resp = client
+        .get("/tmp/cookies.socket", "/test")
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(resp.cookies().next().unwrap().value(), "42");
+
+    let resp = client
+        .get("/tmp/cookies.socket", "/test")
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(resp.text().await.unwrap(), "42");
+
+    let resp = Client::new()
+        .get("/tmp/cookies.socket", "/test")
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(resp.cookies().count(), 1);
+
+    drop(server);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn response_peer_credentials() -> io::Result<()> {
+    let server = server::setup_test_server("peer", "/test", Method::GET, async || {
+        HttpResponse::NoContent().finish()
+    })
+    .await?;
+
+    let resp = Client::new()
+        .get("/tmp/peer.socket", "/test")
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+
+    let credentials = resp.peer_credentials().unwrap();
+    assert_eq!(credentials.pid(), Some(std::process::id() as i32));
+    assert_eq!(
+        resp.peer_path(),
+        Some(std::path::Path::new("/tmp/peer.socket"))
+    );
+
+    drop(server);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn peer_verification() -> io::Result<()> {
+    let server = server::setup_test_server("verify", "/test", Method::GET, async || {
+        HttpResponse::NoContent().finish()
+    })
+    .await?;
+
+    let uid = Client::new()
+        .get("/tmp/verify.socket", "/test")
+        .send()
+        .await
+        .map_err(io::Error::other)?
+        .peer_credentials()
+        .unwrap()
+        .uid();
+
+    let resp = Client::builder()
+        .require_peer_uid(uid)
+        .build()
+        .map_err(io::Error::other)?
+        .get("/tmp/verify.socket", "/test")
+        .bearer_auth("secret")
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert!(resp.status().is_success());
+
+    let err = Client::builder()
+        .require_peer_uid(uid.wrapping_add(1))
+        .build()
+        .map_err(io::Error::other)?
+        .get("/tmp/verify.socket", "/test")
+        .bearer_auth("secret")
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.is_peer_verification());
+    assert!(!err.is_connect());
+    let http_unix_client::Error::PeerVerification(rejected) = err else {
+        panic!("unexpected {err:?}");
+    };
+    let rejected: http_unix_client::PeerVerificationError = rejected;
+    assert_eq!(rejected.credentials().map(|cred| cred.uid()), Some(uid));
+
+    drop(server);
+
+    Ok(())
+}
+
+#[actix_web::test]
+#[cfg(target_os = "linux")]
+async fn abstract_socket() -> io::Result<()> {
+    use http_unix_client::UnixUrl;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::UnixListener;
+
+    let name = format!("http_unix_client_test_{}", std::process::id());
+    let listener = UnixListener::bind(format!("\0{name}"))?;
+    let server = actix_web::rt::spawn(async move {
+        let (mut stream, _) = listener.accept().await?;
+        let mut buf = [0; 1024];
+        let _ = stream.read(&mut buf).await?;
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 8\r\n\r\nabstract")
+            .await
+    });
+
+    let url = UnixUrl::abstract_socket(&name, "/test").map_err(io::Error::other)?;
+    let request = http_unix_client::Request::new(http_unix_client::Method::GET, url);
+    let resp = Client::new()
+        .execute(request)
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(resp.text().await.unwrap(), "abstract");
+
+    server.await??;
+
+    Ok(())
+}
+
+#[actix_web::test]
+#[cfg(feature = "http2")]
+async fn http2_prior_knowledge() -> io::Result<()> {
+    use http_unix_client::Version;
+    use hyper::server::conn::http2;
+    use hyper::service::service_fn;
+    use hyper_util::rt::{TokioExecutor, TokioIo};
+    use tokio::net::UnixListener;
+
+    let socket_path = "/tmp/http2.socket";
+    let _ = std::fs::remove_file(socket_path);
+    let listener = UnixListener::bind(socket_path)?;
+    let server = actix_web::rt::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let service = service_fn(async |req: hyper::Request<hyper::body::Incoming>| {
+                let body = format!("{:?}", req.version());
+                Ok::<_, io::Error>(hyper::Response::new(body))
+            });
+            actix_web::rt::spawn(
+                http2::Builder::new(TokioExecutor::new())
+                    .serve_connection(TokioIo::new(stream), service),
+            );
+        }
+        #[allow(unreachable_code)]
+        io::Result::Ok(())
+    });
+
+    let resp = Client::new()
+        .get(socket_path, "/")
+        .version(Version::HTTP_2)
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(resp.version(), Version::HTTP_2);
+    assert_eq!(resp.text().await.unwrap(), "HTTP/2.0");
+
+    let resp = Client::builder()
+        .http2_prior_knowledge()
+        .build()
+        .map_err(io::Error::other)?
+        .get(socket_path, "/")
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(resp.version(), Version::HTTP_2);
+
+    server.abort();
+    let _ = std::fs::remove_file(socket_path);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn upgrade() -> io::Result<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::UnixListener;
+
+    let socket_path = "/tmp/upgrade.socket";
+    let _ = std::fs::remove_file(socket_path);
+    let listener = UnixListener::bind(socket_path)?;
+    let server = actix_web::rt::spawn(async move {
+        let (mut stream, _) = listener.accept().await?;
+        let mut buf = [0; 1024];
+        let n = stream.read(&mut buf).await?;
+        let request = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+        assert!(request.contains("connection: upgrade\r\n"));
+        assert!(request.contains("upgrade: tcp\r\n"));
+
+        stream
+            .write_all(
+                b"HTTP/1.1 101 Switching Protocols\r\nconnection: upgrade\r\nupgrade: tcp\r\n\r\n",
+            )
+            .await?;
+        let n = stream.read(&mut buf).await?;
+        stream.write_all(&buf[..n]).await
+    });
+
+    let mut upgraded = Client::new()
+        .post(socket_path, "/attach")
+        .upgrade("tcp")
+        .send()
+        .await
+        .map_err(io::Error::other)?
+        .upgrade()
+        .await
+        .map_err(io::Error::other)?;
+
+    upgraded.write_all(b"ping").await?;
+    let mut echo = [0; 4];
+    upgraded.read_exact(&mut echo).await?;
+    assert_eq!(&echo, b"ping");
+
+    server.await??;
+    let _ = std::fs::remove_file(socket_path);
+
+    Ok(())
+}
+
+#[actix_web::test]
+#[cfg(feature = "websocket")]
+async fn websocket() -> io::Result<()> {
+    use futures_util::{SinkExt, StreamExt};
+    use http_unix_client::websocket::Message;
+    use tokio::net::UnixListener;
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+
+    let socket_path = "/tmp/websocket.socket";
+    let _ = std::fs::remove_file(socket_path);
+    let listener = UnixListener::bind(socket_path)?;
+    let server = actix_web::rt::spawn(async move {
+        let (stream, _) = listener.accept().await?;
+        #[allow(clippy::result_large_err)]
+        let check_auth = |req: &Request, resp: Response| {
+            assert_eq!(req.uri().path(), "/events");
+            assert_eq!(req.headers()["authorization"], "Bearer token");
+            Ok(resp)
+        };
+        let mut ws = tokio_tungstenite::accept_hdr_async(stream, check_auth)
+            .await
+            .map_err(io::Error::other)?;
+        while let Some(message) = ws.next().await {
+            let message = message.map_err(io::Error::other)?;
+            if message.is_text() || message.is_binary() {
+                ws.send(message).await.map_err(io::Error::other)?;
+            }
+        }
+        io::Result::Ok(())
+    });
+
+    let mut ws = Client::new()
+        .get(socket_path, "/events")
+        .bearer_auth("token")
+        .websocket()
+        .await
+        .map_err(io::Error::other)?;
+
+    ws.send(Message::Text("hello".into()))
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(
+        ws.next().await.unwrap().map_err(io::Error::other)?,
+        Message::Text("hello".into())
+    );
+    ws.close().await.map_err(io::Error::other)?;
+
+    server.await??;
+    let _ = std::fs::remove_file(socket_path);
+
+    let err = Client::new()
+        .get("/tmp/missing.socket", "/events")
+        .websocket()
+        .await
+        .unwrap_err();
+    assert!(err.is_connect());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn server_sent_events() -> io::Result<()> {
+    use futures_util::StreamExt;
+
+    let server = server::setup_test_server("sse", "/events", Method::GET, async || {
+        HttpResponse::Ok().content_type("text/event-stream").body(
+            ": hello\r\nevent: update\r\ndata: first\r\ndata: second\r\n\r\nid: 7\ndata: third\n\n",
+        )
+    })
+    .await?;
+
+    let events: Vec<_> = Client::new()
+        .get("/tmp/sse.socket", "/events")
+        .send()
+        .await
+        .map_err(io::Error::other)?
+        .events()
+        .map(|event| event.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].event, "update");
+    assert_eq!(events[0].data, "first\nsecond");
+    assert_eq!(events[0].id, None);
+    assert_eq!(events[1].event, "message");
+    assert_eq!(events[1].data, "third");
+    assert_eq!(events[1].id.as_deref(), Some("7"));
+
+    drop(server);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn event_source_reconnects() -> io::Result<()> {
+    use futures_util::StreamExt;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::UnixListener;
+
+    let socket_path = "/tmp/event_source.socket";
+    let _ = std::fs::remove_file(socket_path);
+    let listener = UnixListener::bind(socket_path)?;
+    let server = actix_web::rt::spawn(async move {
+        let responses: [&[u8]; 3] = [
+            b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\nconnection: close\r\n\r\nretry: 10\nid: 1\ndata: a\n\n",
+            b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\nconnection: close\r\n\r\ndata: b\n\n",
+            b"HTTP/1.1 204 No Content\r\n\r\n",
+        ];
+        let mut requests = Vec::new();
+        for response in responses {
+            let (mut stream, _) = listener.accept().await?;
+            let mut buf = [0; 1024];
+            let n = stream.read(&mut buf).await?;
+            requests.push(String::from_utf8_lossy(&buf[..n]).to_lowercase());
+            stream.write_all(response).await?;
+        }
+        io::Result::Ok(requests)
+    });
+
+    let events: Vec<_> = Client::new()
+        .get(socket_path, "/events")
+        .event_source()
+        .map(|event| event.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].data, "a");
+    assert_eq!(events[0].retry, Some(std::time::Duration::from_millis(10)));
+    assert_eq!(events[1].data, "b");
+    assert_eq!(events[1].id.as_deref(), Some("1"));
+
+    let requests = server.await??;
+    assert!(requests[0].contains("accept: text/event-stream\r\n"));
+    assert!(!requests[0].contains("last-event-id"));
+    assert!(requests[1].contains("last-event-id: 1\r\n"));
+    assert!(requests[2].contains("last-event-id: 1\r\n"));
+    let _ = std::fs::remove_file(socket_path);
+
+    Ok(())
+}
+
+#[actix_web::test]
+#[cfg(feature = "json")]
+async fn response_json_lines() -> io::Result<()> {
+    use futures_util::StreamExt;
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Progress {
+        step: u32,
+    }
+
+    let server = server::setup_test_server("json_lines", "/build", Method::GET, async || {
+        HttpResponse::Ok()
+            .content_type("application/x-ndjson")
+            .body("{\"step\":1}\n\n{\"step\":2}\r\n{\"step\":\"three\"}\n{\"step\":4}")
+    })
+    .await?;
+
+    let values: Vec<_> = Client::new()
+        .get("/tmp/json_lines.socket", "/build")
+        .send()
+        .await
+        .map_err(io::Error::other)?
+        .json_lines::<Progress>()
+        .collect()
+        .await;
+
+    assert_eq!(values.len(), 4);
+    assert_eq!(values[0].as_ref().unwrap(), &Progress { step: 1 });
+    assert_eq!(values[1].as_ref().unwrap(), &Progress { step: 2 });
+    let err = values[2].as_ref().unwrap_err();
+    assert!(err.to_string().starts_with("error decoding line 4"));
+    assert_eq!(values[3].as_ref().unwrap(), &Progress { step: 4 });
+
+    drop(server);
+
+    Ok(())
+}
+
+#[actix_web::test]
+#[cfg(feature = "multipart")]
+async fn multipart() -> io::Result<()> {
+    use actix_web::{HttpRequest, web};
+    use http_unix_client::multipart::{Form, Part};
+
+    let server = server::setup_test_server(
+        "multipart",
+        "/upload",
+        Method::POST,
+        async |req: HttpRequest, body: web::Bytes| {
+            let header = |name| {
+                req.headers()
+                    .get(name)
+                    .map_or("", |value| value.to_str().unwrap())
+                    .to_owned()
+            };
+            HttpResponse::Ok()
+                .insert_header(("x-content-type", header("content-type")))
+                .insert_header(("x-content-length", header("content-length")))
+                .body(body)
+        },
+    )
+    .await?;
+
+    let path = std::env::temp_dir().join("http_unix_client_multipart.txt");
+    tokio::fs::write(&path, "file content").await?;
+    let form = Form::new().text("tag", "latest").part(
+        "file",
+        Part::file(&path).await?.mime_str("text/plain").unwrap(),
+    );
+    let boundary = form.boundary().to_owned();
+
+    let resp = Client::new()
+        .post("/tmp/multipart.socket", "/upload")
+        .multipart(form)
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+
+    assert_eq!(
+        resp.headers()["x-content-type"],
+        format!("multipart/form-data; boundary={boundary}").as_str()
+    );
+    let content_length = resp.headers()["x-content-length"]
+        .to_str()
+        .unwrap()
+        .to_owned();
+    let body = resp.text().await.map_err(io::Error::other)?;
+    assert_eq!(content_length, body.len().to_string());
+    assert_eq!(
+        body,
+        format!(
+            "--{boundary}\r\n\
+             Content-Disposition: form-data; name=\"tag\"\r\n\r\n\
+             latest\r\n\
+             --{boundary}\r\n\
+             Content-Disposition: form-data; name=\"file\"; filename=\"http_unix_client_multipart.txt\"\r\n\
+             Content-Type: text/plain\r\n\r\n\
+             file content\r\n\
+             --{boundary}--\r\n"
+        )
+    );
+
+    tokio::fs::remove_file(&path).await?;
+    drop(server);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn middleware() -> io::Result<()> {
+    use actix_web::HttpRequest;
+    use http_unix_client::middleware::{BoxFuture, Middleware, Next};
+    use http_unix_client::{Error, Request, Response};
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct RequestId {
+        statuses: Mutex<Vec<u16>>,
+    }
+
+    impl Middleware for RequestId {
+        fn handle<'a>(&'a self, mut request: Request, next: Next<'a>) -> BoxFuture<'a> {
+            request
+                .headers_mut()
+                .insert("x-request-id", "42".parse().unwrap());
+            Box::pin(async move {
+                let response = next.run(request).await?;
+                self.statuses
+                    .lock()
+                    .unwrap()
+                    .push(response.status().as_u16());
+                Ok(response)
+            })
+        }
+    }
+
+    #[derive(Debug)]
+    struct Cache;
+
+    impl Middleware for Cache {
+        fn handle<'a>(&'a self, request: Request, next: Next<'a>) -> BoxFuture<'a> {
+            match request.url().path() {
+                "/cached" => {
+                    let url = request.url().clone();
+                    Box::pin(
+                        async move { Ok(Response::from_http(hyper::Response::new("cached"), url)) },
+                    )
+                }
+                "/forbidden" => Box::pin(async { Err(Error::middleware("forbidden")) }),
+                _ => next.run(request),
+            }
+        }
+    }
+
+    let server = server::setup_test_server(
+        "middleware",
+        "/{path}",
+        Method::GET,
+        async |req: HttpRequest| {
+            let id = req.headers().get("x-request-id").unwrap().to_str().unwrap();
+            HttpResponse::Ok().body(format!("request {id}"))
+        },
+    )
+    .await?;
+
+    let request_id = std::sync::Arc::new(RequestId::default());
+    let client = Client::builder()
+        .middleware(request_id.clone())
+        .middleware(Cache)
+        .build()
+        .map_err(io::Error::other)?;
+
+    let text = |path| {
+        let client = client.clone();
+        async move {
+            client
+                .get("/tmp/middleware.socket", path)
+                .send()
+                .await?
+                .text()
+                .await
+        }
+    };
+    assert_eq!(text("/sent").await.map_err(io::Error::other)?, "request 42");
+    assert_eq!(text("/cached").await.map_err(io::Error::other)?, "cached");
+    let err = text("/forbidden").await.unwrap_err();
+    assert!(err.is_middleware());
+    assert_eq!(err.to_string(), "middleware error: forbidden");
+    assert_eq!(*request_id.statuses.lock().unwrap(), [200, 200]);
+
+    drop(server);
+
+    Ok(())
+}
+
+#[actix_web::test]
+#[cfg(feature = "tower")]
+async fn tower_service() -> io::Result<()> {
+    use futures_util::future::poll_fn;
+    use http_unix_client::{Body, Request, UnixUrl};
+    use std::convert::Infallible;
+    use std::future::{Ready, ready};
+    use std::task::{Context, Poll};
+    use tower_service::Service;
+
+    let server = server::setup_test_server("tower", "/test", Method::GET, async || {
+        HttpResponse::Ok().body("service")
+    })
+    .await?;
+
+    let mut client = Client::new();
+    poll_fn(|cx| client.poll_ready(cx))
+        .await
+        .map_err(io::Error::other)?;
+    let url = UnixUrl::new("/tmp/tower.socket", "/test").map_err(io::Error::other)?;
+    let resp = client
+        .call(Request::new(http_unix_client::Method::GET, url))
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(resp.text().await.unwrap(), "service");
+
+    #[derive(Clone)]
+    struct Stub;
+
+    impl Service<hyper::Request<Body>> for Stub {
+        type Response = hyper::Response<String>;
+        type Error = Infallible;
+        type Future = Ready<Result<Self::Response, Infallible>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: hyper::Request<Body>) -> Self::Future {
+            let body = format!("{} {}", req.uri().scheme_str().unwrap(), req.uri().path());
+            ready(Ok(hyper::Response::new(body)))
+        }
+    }
+
+    let resp = Client::builder()
+        .transport(Stub)
+        .build()
+        .map_err(io::Error::other)?
+        .get("/tmp/no-such.socket", "/stub")
+        .send()
+        .await
+        .map_err(io::Error::other)?;
+    assert_eq!(resp.text().await.unwrap(), "unix /stub");
+
+    #[derive(Clone)]
+    struct Failing;
+
+    impl Service<hyper::Request<Body>> for Failing {
+        type Response = hyper::Response<String>;
+        type Error = io::Error;
+        type Future = Ready<io::Result<Self::Response>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _req: hyper::Request<Body>) -> Self::Future {
+            ready(Err(io::Error::other("unreachable")))
+        }
+    }
+
+    let err = Client::builder()
+        .transport(Failing)
+        .build()
+        .map_err(io::Error::other)?
+        .get("/tmp/tower.socket", "/test")
+        .send()
+        .await
+        .unwrap_err();
+    assert_eq!(err.to_string(), "transport error: unreachable");
+
+    drop(server);
+
+    Ok(())
+}
+
+#[actix_web::test]
+#[cfg(feature = "tracing")]
+async fn tracing() -> io::Result<()> {
+    use std::fmt::{self, Write};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    #[derive(Default)]
+    struct Fields(String);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct Capture {
+        logs: Arc<Mutex<Vec<String>>>,
+        ids: Arc<AtomicU64>,
+    }
+
+    impl Capture {
+        fn push(&self, kind: &str, record: impl FnOnce(&mut Fields)) {
+            let mut fields = Fields::default();
+            record(&mut fields);
+            self.logs
+                .lock()
+                .unwrap()
+                .push(format!("{kind}{}", fields.0));
+        }
+    }
+
+    impl Subscriber for Capture {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            self.push(span.metadata().name(), |fields| span.record(fields));
+            Id::from_u64(self.ids.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _span: &Id, values: &Record<'_>) {
+            self.push("record", |fields| values.record(fields));
+        }
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            self.push("event", |fields| event.record(fields));
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    let server = server::setup_test_server("tracing", "/test", Method::GET, async || {
+        HttpResponse::Ok().body("traced")
+    })
+    .await?;
+
+    let capture = Capture::default();
+    let _guard = tracing::subscriber::set_default(capture.clone());
+
+    let client = Client::new();
+    for _ in 0..2 {
+        let resp = client
+            .get("/tmp/tracing.socket", "/test")
+            .bearer_auth("secret-token")
+            .send()
+            .await
+            .map_err(io::Error::other)?;
+        assert_eq!(resp.text().await.unwrap(), "traced");
+    }
+
+    let logs = capture.logs.lock().unwrap().join("\n");
+    assert!(logs.contains("request method=GET socket=/tmp/tracing.socket path=\"/test\""));
+    assert!(logs.contains("record status=200"));
+    assert!(logs.contains("record duration="));
+    assert_eq!(logs.matches("message=connected").count(), 1);
+    assert!(
+        logs.contains("message=reused pooled connection socket=/tmp/tracing.socket requests=2")
+    );
+    assert_eq!(
+        logs.matches("message=response body read bytes=6").count(),
+        2
+    );
+    assert!(logs.contains("Sensitive"));
+    assert!(!logs.contains("secret-token"));
+
+    #[cfg(feature = "cookies")]
+    {
+        use http_unix_client::{UnixUrl, cookie::Jar};
+        use std::sync::Arc;
+
+        let url = UnixUrl::new("/tmp/tracing.socket", "/test").map_err(io::Error::other)?;
+        let jar = Arc::new(Jar::default());
+        jar.add_cookie_str("session=secret-cookie", &url);
+        let resp = Client::builder()
+            .cookie_provider(jar)
+            .build()
+            .map_err(io::Error::other)?
+            .get("/tmp/tracing.socket", "/test")
+            .send()
+            .await
+            .map_err(io::Error::other)?;
+        assert_eq!(resp.text().await.unwrap(), "traced");
+
+        let logs = capture.logs.lock().unwrap().join("\n");
+        assert!(!logs.contains("secret-cookie"));
+    }
+
+    drop(server);
+
+    Ok(())
+}